@@ -1,20 +1,26 @@
 use async_trait::async_trait;
-use reqwest::RequestBuilder;
+use http::{header, request::Parts};
 
-use crate::{http_client::HttpRequestOption, Drupalkit};
+use crate::{http_client::HttpRequestOption, Drupalkit, Secret};
 
-use super::{strategy::AuthStrategyResult, AuthStrategy};
+use super::{
+    strategy::{basic_auth, AuthStrategyResult},
+    AuthStrategy,
+};
 
 pub struct BasicAuthStrategy {
     username: String,
-    password: Option<String>,
+    password: Option<Secret>,
 }
 
 impl BasicAuthStrategy {
-    pub fn new(username: &str, password: Option<&str>) -> Self {
+    pub fn new<P>(username: &str, password: Option<P>) -> Self
+    where
+        P: Into<Secret>,
+    {
         Self {
             username: username.to_string(),
-            password: password.map(|password| password.to_string()),
+            password: password.map(Into::into),
         }
     }
 }
@@ -23,13 +29,17 @@ impl BasicAuthStrategy {
 impl AuthStrategy for BasicAuthStrategy {
     async fn set_auth_info(
         &mut self,
-        req_builder: RequestBuilder,
+        parts: &mut Parts,
         _path: &str,
         _options: Vec<HttpRequestOption>,
         _drupalkit: &Drupalkit,
     ) -> AuthStrategyResult {
-        let password = self.password.clone();
+        let value = basic_auth(
+            &self.username,
+            self.password.as_ref().map(Secret::expose_secret),
+        )?;
+        parts.headers.insert(header::AUTHORIZATION, value);
 
-        Ok(req_builder.basic_auth(&self.username, password))
+        Ok(())
     }
 }
@@ -1,18 +1,21 @@
 use async_trait::async_trait;
-use reqwest::RequestBuilder;
+use http::{header, request::Parts};
 
-use crate::{http_client::HttpRequestOption, Drupalkit};
+use crate::{http_client::HttpRequestOption, Drupalkit, Secret};
 
-use super::{strategy::AuthStrategyResult, AuthStrategy};
+use super::{
+    strategy::{bearer_auth, AuthStrategyResult},
+    AuthStrategy,
+};
 
 pub struct BearerAuthStrategy {
-    token: String,
+    token: Secret,
 }
 
 impl BearerAuthStrategy {
-    pub fn new(token: &str) -> Self {
+    pub fn new(token: impl Into<Secret>) -> Self {
         Self {
-            token: token.to_string(),
+            token: token.into(),
         }
     }
 }
@@ -21,11 +24,16 @@ impl BearerAuthStrategy {
 impl AuthStrategy for BearerAuthStrategy {
     async fn set_auth_info(
         &mut self,
-        req_builder: RequestBuilder,
+        parts: &mut Parts,
         _path: &str,
         _options: Vec<HttpRequestOption>,
         _drupalkit: &Drupalkit,
     ) -> AuthStrategyResult {
-        Ok(req_builder.bearer_auth(&self.token))
+        parts.headers.insert(
+            header::AUTHORIZATION,
+            bearer_auth(self.token.expose_secret())?,
+        );
+
+        Ok(())
     }
 }
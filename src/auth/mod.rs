@@ -1,7 +1,12 @@
 mod basic;
 mod bearer;
+mod session;
 mod strategy;
 
 pub use basic::BasicAuthStrategy;
 pub use bearer::BearerAuthStrategy;
+pub use session::SessionAuthStrategy;
 pub use strategy::{AuthStrategy, AuthStrategyError, AuthStrategyResult};
+
+#[cfg(feature = "simple-oauth")]
+pub(crate) use strategy::bearer_auth;
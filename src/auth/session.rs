@@ -0,0 +1,321 @@
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{header, request::Parts, HeaderMap, HeaderName, HeaderValue, Method};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{
+    error::FailedRequestError,
+    http_client::{HttpClient, HttpRequestOption},
+    transport::ResponseExt,
+    Drupalkit, DrupalkitError, Secret,
+};
+
+use super::{
+    strategy::{sensitive, AuthStrategyResult},
+    AuthStrategy, AuthStrategyError,
+};
+
+/// Authenticates requests with a session cookie of Drupal core's
+/// cookie authentication.
+///
+/// Logs in using `POST /user/login?_format=json` before the first
+/// request and attaches the session cookie to every request. Logs in
+/// again once the session cookie expired or a request was answered
+/// with `401 Unauthorized`. Use `Drupalkit::logout` to end the session.
+///
+/// Unsafe requests, e.g. `POST` or `PATCH`, automatically get the
+/// `X-CSRF-Token` header required by Drupal core's REST module.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "reqwest")] {
+/// use drupal_kit::auth::SessionAuthStrategy;
+/// use drupal_kit::Builder;
+///
+/// let mut drupalkit = Builder::new()
+///     .set_base_url("https://example.com")
+///     .build();
+///
+/// drupalkit.set_auth_strategy(SessionAuthStrategy::new("admin", "secret"));
+/// # }
+/// ```
+pub struct SessionAuthStrategy {
+    session: Option<Session>,
+
+    username: String,
+    password: Secret,
+}
+
+/// A session started by logging in.
+#[derive(Debug, Clone)]
+struct Session {
+    /// The `name=value` pairs of the cookies set by the login,
+    /// as sent in the `Cookie` header.
+    cookie: String,
+    csrf_token: String,
+    logout_token: String,
+    /// When the first of the session cookies expires,
+    /// `None` for cookies expiring with the browser session.
+    expires_at: Option<SystemTime>,
+}
+
+impl Session {
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= SystemTime::now())
+    }
+}
+
+#[derive(Serialize)]
+struct LoginRequest<'a> {
+    name: &'a str,
+    pass: &'a str,
+}
+
+#[derive(Deserialize)]
+struct LoginResponse {
+    csrf_token: String,
+    logout_token: String,
+}
+
+impl SessionAuthStrategy {
+    pub fn new(username: &str, password: impl Into<Secret>) -> Self {
+        Self {
+            session: None,
+            username: username.to_owned(),
+            password: password.into(),
+        }
+    }
+
+    async fn login(&self, drupalkit: &Drupalkit) -> Result<Session, DrupalkitError> {
+        let credentials = LoginRequest {
+            name: &self.username,
+            pass: self.password.expose_secret(),
+        };
+
+        // Every character might be escaped, reserve enough capacity
+        // so the buffer is never reallocated, leaving copies behind.
+        let mut body = Zeroizing::new(Vec::with_capacity(
+            32 + 6 * (credentials.name.len() + credentials.pass.len()),
+        ));
+        serde_json::to_writer(&mut *body, &credentials)
+            .map_err(|err| AuthStrategyError::new(Box::new(err)))?;
+
+        // This MUST be an anonymous request, otherwise the request
+        // would wait for this strategy, which is busy logging in.
+        let response = drupalkit
+            .request(
+                Method::POST,
+                "/user/login",
+                // The body is zeroized once the request was sent.
+                Bytes::from_owner(body),
+                vec![
+                    HttpRequestOption::Anonymous,
+                    json_format(),
+                    HttpRequestOption::Header(
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static("application/json"),
+                    ),
+                ],
+            )
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(FailedRequestError::from_response(response).await?.into());
+        }
+
+        let (cookie, expires_at) = session_cookie(response.headers()).ok_or_else(|| {
+            AuthStrategyError::new("login response did not set a session cookie".into())
+        })?;
+
+        let login: LoginResponse = response.json().await?;
+
+        Ok(Session {
+            cookie,
+            csrf_token: login.csrf_token,
+            logout_token: login.logout_token,
+            expires_at,
+        })
+    }
+}
+
+#[async_trait]
+impl AuthStrategy for SessionAuthStrategy {
+    async fn set_auth_info(
+        &mut self,
+        parts: &mut Parts,
+        _path: &str,
+        _options: Vec<HttpRequestOption>,
+        drupalkit: &Drupalkit,
+    ) -> AuthStrategyResult {
+        let session = match self.session.take() {
+            Some(session) if !session.is_expired() => {
+                #[cfg(feature = "tracing")]
+                crate::trace::record_auth("cached_session");
+
+                session
+            }
+            _ => {
+                let session = self
+                    .login(drupalkit)
+                    .await
+                    .map_err(|err| AuthStrategyError::new(Box::new(err)))?;
+
+                #[cfg(feature = "tracing")]
+                crate::trace::record_auth("fresh_login");
+
+                // Use the CSRF token of the login, so it does not
+                // have to be fetched from `/session/token`.
+                drupalkit
+                    .csrf_token
+                    .set(
+                        sensitive(session.cookie.clone())?,
+                        sensitive(session.csrf_token.clone())?,
+                    )
+                    .await;
+
+                session
+            }
+        };
+
+        parts
+            .headers
+            .insert(header::COOKIE, sensitive(session.cookie.clone())?);
+
+        self.session = Some(session);
+
+        Ok(())
+    }
+
+    async fn on_unauthorized(
+        &mut self,
+        _path: &str,
+        _options: Vec<HttpRequestOption>,
+        _drupalkit: &Drupalkit,
+    ) -> bool {
+        // The session might have been destroyed before the
+        // cookie expired, e.g. by a logout in another client.
+        self.session = None;
+
+        true
+    }
+
+    async fn logout(&mut self, drupalkit: &Drupalkit) -> AuthStrategyResult {
+        let Some(session) = self.session.take() else {
+            return Ok(());
+        };
+
+        let response = drupalkit
+            .request(
+                Method::POST,
+                "/user/logout",
+                "",
+                vec![
+                    HttpRequestOption::Anonymous,
+                    json_format(),
+                    HttpRequestOption::Query(vec![("token".to_owned(), session.logout_token)]),
+                    HttpRequestOption::Header(header::COOKIE, sensitive(session.cookie)?),
+                ],
+            )
+            .await?;
+
+        // The session is gone already if it expired meanwhile.
+        if !response.status().is_success() && response.status() != http::StatusCode::FORBIDDEN {
+            return Err(FailedRequestError::from_response(response).await?.into());
+        }
+
+        Ok(())
+    }
+}
+
+fn json_format() -> HttpRequestOption {
+    HttpRequestOption::Query(vec![("_format".to_owned(), "json".to_owned())])
+}
+
+/// Extracts the `Cookie` header value and the expiry of the
+/// cookies from the `Set-Cookie` headers of the login response.
+fn session_cookie(headers: &HeaderMap) -> Option<(String, Option<SystemTime>)> {
+    let mut pairs = Vec::new();
+    let mut expires_at: Option<SystemTime> = None;
+
+    for value in headers.get_all(HeaderName::from_static("set-cookie")) {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+
+        let mut attributes = value.split(';').map(str::trim);
+
+        let Some(pair) = attributes.next().filter(|pair| pair.contains('=')) else {
+            continue;
+        };
+
+        pairs.push(pair.to_owned());
+
+        let mut max_age = None;
+        let mut expires = None;
+
+        for attribute in attributes {
+            let (name, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+
+            if name.eq_ignore_ascii_case("max-age") {
+                max_age = value
+                    .parse::<u64>()
+                    .ok()
+                    .map(|seconds| SystemTime::now() + Duration::from_secs(seconds));
+            } else if name.eq_ignore_ascii_case("expires") {
+                expires = httpdate::parse_http_date(value).ok();
+            }
+        }
+
+        // `Max-Age` takes precedence over `Expires`.
+        if let Some(cookie_expires_at) = max_age.or(expires) {
+            expires_at = Some(match expires_at {
+                Some(expires_at) => expires_at.min(cookie_expires_at),
+                None => cookie_expires_at,
+            });
+        }
+    }
+
+    match pairs.is_empty() {
+        true => None,
+        false => Some((pairs.join("; "), expires_at)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_cookie() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            "set-cookie",
+            HeaderValue::from_static(
+                "SESSabc=123; expires=Wed, 21 Oct 2065 07:28:00 GMT; Max-Age=2000000; path=/; HttpOnly",
+            ),
+        );
+        headers.append("set-cookie", HeaderValue::from_static("has_js=1; path=/"));
+
+        let (cookie, expires_at) = session_cookie(&headers).unwrap();
+
+        assert_eq!(cookie, "SESSabc=123; has_js=1");
+
+        let expires_in = expires_at
+            .unwrap()
+            .duration_since(SystemTime::now())
+            .unwrap();
+
+        assert!(expires_in <= Duration::from_secs(2000000));
+        assert!(expires_in > Duration::from_secs(1999990));
+    }
+
+    #[test]
+    fn test_session_cookie_missing() {
+        assert!(session_cookie(&HeaderMap::new()).is_none());
+    }
+}
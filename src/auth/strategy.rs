@@ -5,12 +5,14 @@ use std::{
 };
 
 use async_trait::async_trait;
-use reqwest::RequestBuilder;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use http::{request::Parts, HeaderValue};
 use tokio::sync::Mutex;
+use zeroize::Zeroizing;
 
-use crate::{http_client::HttpRequestOption, Drupalkit};
+use crate::{csrf::CsrfToken, http_client::HttpRequestOption, Drupalkit, DrupalkitError};
 
-pub type AuthStrategyResult = Result<RequestBuilder, AuthStrategyError>;
+pub type AuthStrategyResult = Result<(), DrupalkitError>;
 
 #[derive(Debug)]
 pub struct AuthStrategyError {
@@ -29,20 +31,47 @@ impl fmt::Display for AuthStrategyError {
     }
 }
 
-impl error::Error for AuthStrategyError {}
+impl error::Error for AuthStrategyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
 
 #[async_trait]
 pub trait AuthStrategy
 where
     Self: Send + Sync,
 {
+    /// Adds the auth info, e.g. an `Authorization` header,
+    /// to the given request parts.
     async fn set_auth_info(
         &mut self,
-        req_builder: RequestBuilder,
+        parts: &mut Parts,
         path: &str,
         options: Vec<HttpRequestOption>,
         drupalkit: &Drupalkit,
     ) -> AuthStrategyResult;
+
+    /// Called when a request authenticated by this strategy
+    /// was answered with `401 Unauthorized`.
+    ///
+    /// Drop any cached credentials and return `true` to replay the
+    /// request once with auth info from a new `set_auth_info` call.
+    async fn on_unauthorized(
+        &mut self,
+        _path: &str,
+        _options: Vec<HttpRequestOption>,
+        _drupalkit: &Drupalkit,
+    ) -> bool {
+        false
+    }
+
+    /// Ends the authenticated session, if the strategy has one.
+    ///
+    /// Called by `Drupalkit::logout`. Drop any cached credentials.
+    async fn logout(&mut self, _drupalkit: &Drupalkit) -> AuthStrategyResult {
+        Ok(())
+    }
 }
 
 impl Drupalkit {
@@ -51,7 +80,82 @@ impl Drupalkit {
         T: AuthStrategy + 'static,
     {
         self.auth_strategy = Some(Arc::new(Mutex::new(auth_strategy)));
+        // The CSRF token belongs to the session of the previous strategy.
+        self.csrf_token = CsrfToken::new();
 
         self
     }
+
+    /// Logs out of the session of the auth strategy, e.g.
+    /// of a `SessionAuthStrategy`.
+    ///
+    /// Does nothing if no auth strategy is set
+    /// or the strategy has no session.
+    ///
+    /// Drops the CSRF token of the session.
+    pub async fn logout(&self) -> AuthStrategyResult {
+        self.csrf_token.clear().await;
+
+        match self.auth_strategy.clone() {
+            Some(auth_strategy) => auth_strategy.lock().await.logout(self).await,
+            None => Ok(()),
+        }
+    }
+}
+
+/// Creates the value of an `Authorization` header using the `Bearer` scheme.
+pub(crate) fn bearer_auth(token: &str) -> Result<HeaderValue, DrupalkitError> {
+    let mut value = Zeroizing::new(String::with_capacity(7 + token.len()));
+    value.push_str("Bearer ");
+    value.push_str(token);
+
+    sensitive(value)
+}
+
+/// Creates the value of an `Authorization` header using the `Basic` scheme.
+pub(crate) fn basic_auth(
+    username: &str,
+    password: Option<&str>,
+) -> Result<HeaderValue, DrupalkitError> {
+    let password = password.unwrap_or_default();
+
+    let mut credentials =
+        Zeroizing::new(String::with_capacity(username.len() + 1 + password.len()));
+    credentials.push_str(username);
+    credentials.push(':');
+    credentials.push_str(password);
+
+    let encoded_len = base64::encoded_len(credentials.len(), true).unwrap_or_default();
+
+    let mut value = Zeroizing::new(String::with_capacity(6 + encoded_len));
+    value.push_str("Basic ");
+    BASE64_STANDARD.encode_string(credentials.as_bytes(), &mut value);
+
+    sensitive(value)
+}
+
+/// Creates a sensitive header value.
+///
+/// The given value is zeroized once the header value was created.
+pub(crate) fn sensitive(
+    value: impl Into<Zeroizing<String>>,
+) -> Result<HeaderValue, DrupalkitError> {
+    let mut value = HeaderValue::from_str(&value.into())?;
+    value.set_sensitive(true);
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_auth() {
+        let value = basic_auth("admin", Some("secret")).unwrap();
+
+        assert_eq!(value, "Basic YWRtaW46c2VjcmV0");
+        assert!(value.is_sensitive());
+        assert_eq!(basic_auth("admin", None).unwrap(), "Basic YWRtaW46");
+    }
 }
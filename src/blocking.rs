@@ -0,0 +1,176 @@
+//! A synchronous `Drupalkit` client.
+//!
+//! Mirrors the async API for synchronous code like build scripts and
+//! small CLIs. Each client drives its requests on its own single threaded
+//! tokio runtime, so it must not be used from within an async context.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use drupal_kit::auth::BasicAuthStrategy;
+//! use drupal_kit::http::Method;
+//!
+//! # fn run() -> Result<(), drupal_kit::DrupalkitError> {
+//! let mut drupalkit = drupal_kit::Builder::new()
+//!     .set_base_url("https://example.com")
+//!     .build_blocking();
+//! drupalkit.set_auth_strategy(BasicAuthStrategy::new("admin", Some("secret")));
+//!
+//! let article: serde_json::Value =
+//!     drupalkit.request_json(Method::GET, "/node/1?_format=json", "", vec![])?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{fmt::Debug, sync::Arc};
+
+use bytes::Bytes;
+use http::Method;
+use serde::de::DeserializeOwned;
+use tokio::runtime::Runtime;
+
+use crate::{
+    auth::AuthStrategy,
+    http_client::{DrupalResponse, HttpClient, HttpRequestOption},
+    transport::Body,
+    DrupalkitError,
+};
+
+#[cfg(feature = "simple-oauth")]
+use crate::simple_oauth::{SimpleOauthGrant, SimpleOauthTokenResponse};
+
+/// A response with a fully read body.
+pub type Response = http::Response<Bytes>;
+
+/// A synchronous `Drupalkit` client.
+///
+/// Wraps an async `crate::Drupalkit`, sharing its configuration,
+/// and blocks the current thread until each request completes.
+/// Clones share the runtime and the auth strategy.
+///
+/// # Panics
+///
+/// Calling any request method from within an async context panics,
+/// as does dropping the last clone there.
+#[derive(Clone)]
+pub struct Drupalkit {
+    inner: crate::Drupalkit,
+    runtime: Arc<Runtime>,
+}
+
+impl Drupalkit {
+    #[cfg(feature = "reqwest")]
+    pub fn new(base_url: &str, #[cfg(feature = "consumer")] client_id: Option<&str>) -> Self {
+        crate::Drupalkit::new(
+            base_url,
+            #[cfg(feature = "consumer")]
+            client_id,
+        )
+        .into()
+    }
+
+    pub fn set_auth_strategy<T>(&mut self, auth_strategy: T) -> &Self
+    where
+        T: AuthStrategy + 'static,
+    {
+        self.inner.set_auth_strategy(auth_strategy);
+
+        self
+    }
+
+    /// The async client this client wraps.
+    pub fn as_async(&self) -> &crate::Drupalkit {
+        &self.inner
+    }
+
+    /// Make an HTTP request and read the response body.
+    ///
+    /// See `HttpClient::request`.
+    pub fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: impl Into<Body> + Send,
+        options: Vec<HttpRequestOption>,
+    ) -> Result<Response, DrupalkitError> {
+        self.runtime.block_on(async {
+            let response = self.inner.request(method, path, body, options).await?;
+            let (parts, body) = response.into_parts();
+
+            Ok(Response::from_parts(parts, body.bytes().await?))
+        })
+    }
+
+    /// The same as `request` but deserializes json response body
+    /// into a struct.
+    ///
+    /// See `HttpClient::request_json`.
+    pub fn request_json<T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: impl Into<Body> + Send,
+        options: Vec<HttpRequestOption>,
+    ) -> Result<T, DrupalkitError>
+    where
+        T: DeserializeOwned + Debug,
+    {
+        self.runtime
+            .block_on(self.inner.request_json(method, path, body, options))
+    }
+
+    /// The same as `request_json` but also returns the response metadata.
+    ///
+    /// See `HttpClient::request_json_with_meta`.
+    pub fn request_json_with_meta<T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: impl Into<Body> + Send,
+        options: Vec<HttpRequestOption>,
+    ) -> Result<DrupalResponse<T>, DrupalkitError>
+    where
+        T: DeserializeOwned,
+    {
+        self.runtime.block_on(
+            self.inner
+                .request_json_with_meta(method, path, body, options),
+        )
+    }
+
+    /// Requests an access token using the given grant.
+    #[cfg(feature = "simple-oauth")]
+    pub fn request_token(
+        &self,
+        grant: SimpleOauthGrant,
+    ) -> Result<SimpleOauthTokenResponse, DrupalkitError> {
+        self.runtime.block_on(self.inner.request_token(grant))
+    }
+
+    /// Removes all cached responses tagged with any of the given
+    /// cache tags and returns the number of removed responses.
+    ///
+    /// See `crate::Drupalkit::invalidate_tags`.
+    pub fn invalidate_tags(&self, tags: &[&str]) -> usize {
+        self.runtime.block_on(self.inner.invalidate_tags(tags))
+    }
+}
+
+impl From<crate::Drupalkit> for Drupalkit {
+    /// Wraps the async client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be created.
+    fn from(inner: crate::Drupalkit) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime must be created for blocking drupalkit");
+
+        Self {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
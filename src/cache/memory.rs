@@ -0,0 +1,227 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+
+use super::{CacheStore, CachedResponse};
+
+struct Entry {
+    response: CachedResponse,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<String, Entry>,
+    /// Keys ordered by last use, the least recently used first.
+    lru: BTreeMap<u64, String>,
+    /// Keys of the entries tagged with a cache tag.
+    tags: HashMap<String, HashSet<String>>,
+    tick: u64,
+}
+
+impl State {
+    fn touch(&mut self, key: &str) {
+        self.tick += 1;
+
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.lru.remove(&entry.last_used);
+            self.lru.insert(self.tick, key.to_owned());
+            entry.last_used = self.tick;
+        }
+    }
+
+    fn insert(&mut self, key: &str, response: CachedResponse) {
+        for tag in &response.tags {
+            self.tags
+                .entry(tag.clone())
+                .or_default()
+                .insert(key.to_owned());
+        }
+
+        self.entries.insert(
+            key.to_owned(),
+            Entry {
+                response,
+                last_used: 0,
+            },
+        );
+        self.touch(key);
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CachedResponse> {
+        let entry = self.entries.remove(key)?;
+        self.lru.remove(&entry.last_used);
+
+        for tag in &entry.response.tags {
+            if let Some(keys) = self.tags.get_mut(tag) {
+                keys.remove(key);
+
+                if keys.is_empty() {
+                    self.tags.remove(tag);
+                }
+            }
+        }
+
+        Some(entry.response)
+    }
+}
+
+/// In-memory cache store evicting the least recently used
+/// entries once the capacity is reached.
+pub struct MemoryCacheStore {
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+impl MemoryCacheStore {
+    /// Creates a store holding up to `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Returns the number of stored responses.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[async_trait]
+impl CacheStore for MemoryCacheStore {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut state = self.lock();
+        let response = state.entries.get(key)?.response.clone();
+        state.touch(key);
+
+        Some(response)
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse) {
+        let mut state = self.lock();
+        state.remove(key);
+
+        while state.entries.len() >= self.capacity {
+            let Some(lru_key) = state.lru.first_key_value().map(|(_, key)| key.clone()) else {
+                break;
+            };
+
+            state.remove(&lru_key);
+        }
+
+        state.insert(key, response);
+    }
+
+    async fn remove(&self, key: &str) {
+        self.lock().remove(key);
+    }
+
+    async fn invalidate_tags(&self, tags: &[&str]) -> usize {
+        let mut state = self.lock();
+
+        let keys: HashSet<String> = tags
+            .iter()
+            .filter_map(|tag| state.tags.get(*tag))
+            .flatten()
+            .cloned()
+            .collect();
+
+        keys.iter()
+            .filter(|key| state.remove(key).is_some())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use bytes::Bytes;
+    use http::{HeaderMap, StatusCode};
+
+    use super::*;
+
+    fn response(body: &'static str) -> CachedResponse {
+        tagged(body, &[])
+    }
+
+    fn tagged(body: &'static str, tags: &[&str]) -> CachedResponse {
+        CachedResponse {
+            url: "https://example.com".parse().unwrap(),
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+            stored_at: SystemTime::now(),
+            max_age: Duration::from_secs(60),
+            vary: Vec::new(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used() {
+        let store = MemoryCacheStore::new(2);
+
+        store.put("a", response("a")).await;
+        store.put("b", response("b")).await;
+
+        // Use "a", so "b" becomes the least recently used entry.
+        assert!(store.get("a").await.is_some());
+
+        store.put("c", response("c")).await;
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a").await.is_some());
+        assert!(store.get("b").await.is_none());
+        assert!(store.get("c").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_remove() {
+        let store = MemoryCacheStore::new(2);
+
+        store.put("a", response("a")).await;
+        store.put("a", response("updated")).await;
+
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get("a").await.unwrap().body, "updated");
+
+        store.remove("a").await;
+
+        assert!(store.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_tags() {
+        let store = MemoryCacheStore::new(2);
+
+        store.put("a", tagged("a", &["node:1", "node_list"])).await;
+        store.put("b", tagged("b", &["node:2", "node_list"])).await;
+
+        assert_eq!(store.invalidate_tags(&["node:1"]).await, 1);
+        assert!(store.get("a").await.is_none());
+        assert!(store.get("b").await.is_some());
+
+        // Evicted and replaced entries are removed from the tag index.
+        store.put("b", tagged("b", &["node:2"])).await;
+        store.put("c", tagged("c", &["node:3"])).await;
+        store.put("d", tagged("d", &["node:4"])).await;
+
+        assert_eq!(store.invalidate_tags(&["node_list", "node:2"]).await, 0);
+        assert_eq!(store.invalidate_tags(&["node:3", "node:4"]).await, 2);
+        assert!(store.lock().tags.is_empty());
+        assert!(store.is_empty());
+    }
+}
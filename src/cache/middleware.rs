@@ -0,0 +1,272 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use http::{header, HeaderMap, HeaderName, Method, StatusCode};
+use url::Url;
+
+use crate::{
+    http_client::HttpRequestOption,
+    middleware::{Middleware, Next, RequestContext},
+    transport::{Request, Response, ResponseExt},
+    DrupalkitError,
+};
+
+use super::{cache_tags, CacheStatus, CacheStore, CachedResponse, ClientCacheStatus};
+
+/// Caches anonymous `GET` requests according to the `Cache-Control`
+/// header of the response and revalidates stale responses using
+/// `If-None-Match` and `If-Modified-Since`.
+///
+/// Only requests no auth strategy applied to are cached or served from
+/// the cache. Requests with credentials set explicitly, i.e. with an
+/// `Authorization` or `Cookie` header option, are not cached either.
+pub(crate) struct CacheMiddleware {
+    store: Arc<dyn CacheStore>,
+}
+
+impl CacheMiddleware {
+    pub(crate) fn new(store: Arc<dyn CacheStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl Middleware for CacheMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        ctx: &RequestContext<'_>,
+        next: Next<'_>,
+    ) -> Result<Response, DrupalkitError> {
+        if !is_cacheable_request(&req, ctx) {
+            let mut response = next.run(req, ctx).await?;
+            CacheStatus::record(&mut response, ClientCacheStatus::Bypass);
+
+            return Ok(response);
+        }
+
+        let key = req.uri().to_string();
+
+        let cached = self
+            .store
+            .get(&key)
+            .await
+            .filter(|cached| cached.matches_vary(req.headers()));
+
+        // Add validators to revalidate a stale response, unless
+        // the request is conditional already.
+        let mut revalidate = None;
+
+        if let Some(cached) = cached {
+            if cached.is_fresh() {
+                return Ok(cached.to_response(ClientCacheStatus::Hit));
+            }
+
+            let headers = req.headers_mut();
+
+            if !headers.contains_key(header::IF_NONE_MATCH)
+                && !headers.contains_key(header::IF_MODIFIED_SINCE)
+            {
+                if let Some(etag) = cached.headers.get(header::ETAG) {
+                    headers.insert(header::IF_NONE_MATCH, etag.clone());
+                }
+
+                if let Some(last_modified) = cached.headers.get(header::LAST_MODIFIED) {
+                    headers.insert(header::IF_MODIFIED_SINCE, last_modified.clone());
+                }
+
+                revalidate = Some(cached);
+            }
+        }
+
+        let request_headers = req.headers().clone();
+        let mut response = next.run(req, ctx).await?;
+
+        if let Some(mut cached) = revalidate {
+            if response.status() == StatusCode::NOT_MODIFIED {
+                // Update the stored response with the headers of the 304 response.
+                for (name, value) in response.headers() {
+                    cached.headers.insert(name.clone(), value.clone());
+                }
+
+                cached.tags = cache_tags(&cached.headers);
+                cached.stored_at = SystemTime::now();
+                cached.max_age = freshness(&cached.headers).unwrap_or_default();
+
+                self.store.put(&key, cached.clone()).await;
+
+                return Ok(cached.to_response(ClientCacheStatus::Revalidated));
+            }
+        }
+
+        let Some(max_age) = storable_freshness(&response) else {
+            CacheStatus::record(&mut response, ClientCacheStatus::Miss);
+
+            return Ok(response);
+        };
+
+        let vary = vary(response.headers(), &request_headers);
+        let tags = cache_tags(response.headers());
+        let url = match response.url() {
+            Some(url) => url.clone(),
+            None => Url::parse(&key)?,
+        };
+        let (parts, body) = response.into_parts();
+        let cached = CachedResponse {
+            url,
+            status: parts.status,
+            headers: parts.headers,
+            body: body.bytes().await?,
+            stored_at: SystemTime::now(),
+            max_age,
+            vary,
+            tags,
+        };
+
+        self.store.put(&key, cached.clone()).await;
+
+        Ok(cached.to_response(ClientCacheStatus::Miss))
+    }
+}
+
+fn is_cacheable_request(req: &Request, ctx: &RequestContext<'_>) -> bool {
+    req.method() == Method::GET
+        && ctx.anonymous
+        && !req.headers().contains_key(header::AUTHORIZATION)
+        && !req.headers().contains_key(header::COOKIE)
+        && !has_directive(req.headers(), "no-store")
+        && !ctx
+            .options
+            .iter()
+            .any(|option| matches!(option, HttpRequestOption::NoCache))
+}
+
+/// Returns how long the response is fresh, if it may be stored.
+///
+/// Responses without freshness information are stored if they
+/// can be revalidated using an `ETag` or `Last-Modified` header.
+fn storable_freshness(response: &Response) -> Option<Duration> {
+    let headers = response.headers();
+
+    if response.status() != StatusCode::OK
+        || has_directive(headers, "no-store")
+        || has_directive(headers, "private")
+        || headers
+            .get_all(header::VARY)
+            .iter()
+            .any(|value| value.as_bytes().trim_ascii() == b"*")
+    {
+        return None;
+    }
+
+    match freshness(headers) {
+        Some(max_age) => Some(max_age),
+        None if headers.contains_key(header::ETAG)
+            || headers.contains_key(header::LAST_MODIFIED) =>
+        {
+            Some(Duration::ZERO)
+        }
+        None => None,
+    }
+}
+
+/// Returns the freshness lifetime from the `Cache-Control` header,
+/// preferring `s-maxage` over `max-age`, as this is a shared cache.
+fn freshness(headers: &HeaderMap) -> Option<Duration> {
+    if has_directive(headers, "no-cache") {
+        return Some(Duration::ZERO);
+    }
+
+    directive_value(headers, "s-maxage")
+        .or_else(|| directive_value(headers, "max-age"))
+        .map(Duration::from_secs)
+}
+
+fn directives(headers: &HeaderMap) -> impl Iterator<Item = String> + '_ {
+    headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|directive| directive.trim().to_ascii_lowercase())
+}
+
+fn has_directive(headers: &HeaderMap, name: &str) -> bool {
+    directives(headers).any(|directive| directive == name)
+}
+
+pub(crate) fn directive_value(headers: &HeaderMap, name: &str) -> Option<u64> {
+    directives(headers).find_map(|directive| {
+        let (key, value) = directive.split_once('=')?;
+
+        match key.trim() == name {
+            true => value.trim().trim_matches('"').parse().ok(),
+            false => None,
+        }
+    })
+}
+
+/// Collects the values of the request headers listed in the `Vary` header.
+fn vary(
+    response_headers: &HeaderMap,
+    request_headers: &HeaderMap,
+) -> Vec<(HeaderName, Option<http::HeaderValue>)> {
+    response_headers
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok())
+        .map(|name| {
+            let value = request_headers.get(&name).cloned();
+
+            (name, value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    fn headers(cache_control: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(cache_control),
+        );
+
+        headers
+    }
+
+    #[test]
+    fn test_freshness() {
+        assert_eq!(
+            freshness(&headers("max-age=300, public")),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            freshness(&headers("public, max-age=300, s-maxage=60")),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            freshness(&headers("max-age=300, no-cache")),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(freshness(&headers("public")), None);
+    }
+
+    #[test]
+    fn test_directives() {
+        let headers = headers("must-revalidate, no-cache, Private");
+
+        assert!(has_directive(&headers, "private"));
+        assert!(has_directive(&headers, "no-cache"));
+        assert!(!has_directive(&headers, "no-store"));
+    }
+}
@@ -0,0 +1,209 @@
+mod memory;
+mod middleware;
+
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use url::Url;
+
+use crate::{transport::Response, Drupalkit};
+
+pub use memory::MemoryCacheStore;
+pub(crate) use middleware::{directive_value, CacheMiddleware};
+
+/// A response stored in a `CacheStore`.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub url: Url,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+    /// When the response was stored or last revalidated.
+    pub stored_at: SystemTime,
+    /// How long the response is fresh after `stored_at`.
+    pub max_age: Duration,
+    /// The request headers listed in the `Vary` response header,
+    /// with the values of the request the response was stored for.
+    pub vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    /// The cache tags from the `X-Drupal-Cache-Tags` header,
+    /// used to invalidate the response.
+    pub tags: Vec<String>,
+}
+
+impl CachedResponse {
+    /// Checks whether the response can be served without revalidation.
+    pub fn is_fresh(&self) -> bool {
+        SystemTime::now()
+            .duration_since(self.stored_at)
+            .is_ok_and(|age| age < self.max_age)
+    }
+
+    /// Checks whether the response was stored for a request
+    /// with the same values of the varying headers.
+    pub fn matches_vary(&self, request_headers: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| request_headers.get(name) == value.as_ref())
+    }
+
+    pub(crate) fn to_response(&self, status: ClientCacheStatus) -> Response {
+        let mut response = Response::new(self.body.clone().into());
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+        response.extensions_mut().insert(self.url.clone());
+
+        CacheStatus::record(&mut response, status);
+
+        response
+    }
+}
+
+/// Stores cached responses.
+///
+/// Implement this to store responses in a shared cache like Redis.
+/// Implementations are responsible for evicting entries.
+#[async_trait]
+pub trait CacheStore
+where
+    Self: Send + Sync + 'static,
+{
+    async fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    async fn put(&self, key: &str, response: CachedResponse);
+
+    async fn remove(&self, key: &str);
+
+    /// Removes all responses tagged with any of the given cache tags
+    /// and returns the number of removed responses.
+    async fn invalidate_tags(&self, tags: &[&str]) -> usize;
+}
+
+impl Drupalkit {
+    /// Removes all cached responses tagged with any of the given
+    /// cache tags and returns the number of removed responses.
+    ///
+    /// Responses are tagged with the tags of the `X-Drupal-Cache-Tags`
+    /// header, which Drupal adds when `http.response.debug_cacheability_headers`
+    /// is enabled or a purge module exposes it.
+    /// Does nothing if no cache store is configured.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use drupal_kit::Drupalkit;
+    ///
+    /// # async fn run(drupalkit: Drupalkit) {
+    /// // Called from a webhook after node 12 was updated.
+    /// drupalkit.invalidate_tags(&["node:12", "node_list"]).await;
+    /// # }
+    /// ```
+    pub async fn invalidate_tags(&self, tags: &[&str]) -> usize {
+        match &self.cache_store {
+            Some(cache_store) => cache_store.invalidate_tags(tags).await,
+            None => 0,
+        }
+    }
+}
+
+/// Parses the space separated cache tags of the `X-Drupal-Cache-Tags` header.
+pub(crate) fn cache_tags(headers: &HeaderMap) -> Vec<String> {
+    space_separated(headers, "x-drupal-cache-tags")
+}
+
+/// Parses the space separated values of a header like `X-Drupal-Cache-Contexts`.
+pub(crate) fn space_separated(headers: &HeaderMap, name: &str) -> Vec<String> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(str::split_whitespace)
+        .map(str::to_owned)
+        .collect()
+}
+
+/// How the response cache of `Drupalkit` handled a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientCacheStatus {
+    /// The response was served from the cache.
+    Hit,
+    /// The response was fetched from Drupal.
+    Miss,
+    /// The cached response was revalidated with Drupal.
+    Revalidated,
+    /// The request was not eligible for caching,
+    /// e.g. because it was authenticated.
+    Bypass,
+}
+
+/// Status of one of Drupal's cache layers, as reported
+/// in the `X-Drupal-Cache` and `X-Drupal-Dynamic-Cache` headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrupalCacheStatus {
+    Hit,
+    Miss,
+    Uncacheable,
+}
+
+impl DrupalCacheStatus {
+    pub(crate) fn from_header(headers: &HeaderMap, name: &str) -> Option<Self> {
+        let value = headers.get(name)?.to_str().ok()?.trim();
+
+        // The dynamic page cache may add a reason, e.g. `UNCACHEABLE (poor cacheability)`.
+        match value.split_whitespace().next()? {
+            "HIT" => Some(Self::Hit),
+            "MISS" => Some(Self::Miss),
+            "UNCACHEABLE" => Some(Self::Uncacheable),
+            _ => None,
+        }
+    }
+}
+
+/// Cache status of a response.
+///
+/// Added to the response extensions of every response
+/// when a cache store is configured.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use drupal_kit::cache::{CacheStatus, ClientCacheStatus};
+/// use drupal_kit::http_client::HttpClient;
+/// use drupal_kit::Drupalkit;
+/// use http::Method;
+///
+/// # async fn run(drupalkit: Drupalkit) -> Result<(), drupal_kit::DrupalkitError> {
+/// let response = drupalkit
+///     .request(Method::GET, "/jsonapi/node/article", "", vec![])
+///     .await?;
+///
+/// if let Some(status) = response.extensions().get::<CacheStatus>() {
+///     println!("served from cache: {}", status.client == ClientCacheStatus::Hit);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStatus {
+    pub client: ClientCacheStatus,
+    /// Status of Drupal's internal page cache.
+    pub page_cache: Option<DrupalCacheStatus>,
+    /// Status of Drupal's dynamic page cache.
+    pub dynamic_page_cache: Option<DrupalCacheStatus>,
+}
+
+impl CacheStatus {
+    pub(crate) fn record(response: &mut Response, client: ClientCacheStatus) {
+        let status = Self {
+            client,
+            page_cache: DrupalCacheStatus::from_header(response.headers(), "x-drupal-cache"),
+            dynamic_page_cache: DrupalCacheStatus::from_header(
+                response.headers(),
+                "x-drupal-dynamic-cache",
+            ),
+        };
+
+        response.extensions_mut().insert(status);
+    }
+}
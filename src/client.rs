@@ -1,92 +1,207 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::Mutex;
 
 use crate::{
     auth::AuthStrategy,
+    cache::CacheStore,
+    csrf::{CsrfToken, CSRF_TOKEN_HEADER_NAME},
     http_client::{HttpClient, HttpRequestOption},
+    language::LangcodeMode,
+    middleware::Middleware,
+    transport::{Response, Transport},
+    DrupalkitError, MaintenanceGate, RateLimiter, RetryPolicy,
 };
-use reqwest::Client;
+
+pub(crate) const DEFAULT_PUBLIC_FILES_PATH: &str = "/sites/default/files";
 
 #[cfg(feature = "consumer")]
 const CONSUMER_HEADER_NAME: &str = "X-Consumer-ID";
 
 #[derive(Clone)]
 pub struct Drupalkit {
-    pub(crate) http_client: Client,
+    pub(crate) transport: Arc<dyn Transport>,
 
     pub(crate) base_url: String,
+    pub(crate) public_files_path: String,
     #[cfg(feature = "consumer")]
     pub(crate) client_id: Option<String>,
 
     pub(crate) auth_strategy: Option<Arc<Mutex<dyn AuthStrategy>>>,
+    pub(crate) csrf_token: Arc<CsrfToken>,
+    pub(crate) default_headers: http::HeaderMap,
+    pub(crate) langcode: Option<String>,
+    pub(crate) langcode_mode: LangcodeMode,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    pub(crate) maintenance_gate: Option<Arc<MaintenanceGate>>,
+    pub(crate) middlewares: Vec<Arc<dyn Middleware>>,
+    pub(crate) cache_store: Option<Arc<dyn CacheStore>>,
+    #[cfg(feature = "tower")]
+    pub(crate) service: Option<crate::service::DrupalService>,
 }
 
 impl Drupalkit {
+    #[cfg(feature = "reqwest")]
     pub fn new(base_url: &str, #[cfg(feature = "consumer")] client_id: Option<&str>) -> Self {
         #[cfg(feature = "consumer")]
         let client_id = client_id.map(|client_id| client_id.to_owned());
 
         Self {
-            http_client: reqwest::Client::new(),
+            transport: Arc::new(crate::transport::ReqwestTransport::default()),
 
             base_url: base_url.to_owned(),
+            public_files_path: DEFAULT_PUBLIC_FILES_PATH.to_owned(),
             #[cfg(feature = "consumer")]
             client_id,
 
             auth_strategy: None,
+            csrf_token: CsrfToken::new(),
+            default_headers: http::HeaderMap::new(),
+            langcode: None,
+            langcode_mode: LangcodeMode::default(),
+            timeout: None,
+            retry_policy: None,
+            rate_limiter: None,
+            maintenance_gate: None,
+            middlewares: Vec::new(),
+            cache_store: None,
+            #[cfg(feature = "tower")]
+            service: None,
         }
     }
 }
 
 impl HttpClient for Drupalkit {
-    fn get_http_client(&self) -> &reqwest::Client {
-        &self.http_client
+    /// Passes the request through the `tower` layer stack
+    /// before running the request flow.
+    #[cfg(feature = "tower")]
+    fn request(
+        &self,
+        method: http::Method,
+        path: &str,
+        body: impl Into<crate::transport::Body> + Send,
+        options: Vec<HttpRequestOption>,
+    ) -> impl std::future::Future<Output = Result<Response, DrupalkitError>> + Send
+    where
+        Self: Sync,
+    {
+        self.dispatch(crate::service::DrupalRequest::new(
+            method, path, body, options,
+        ))
+    }
+
+    fn get_transport(&self) -> &dyn Transport {
+        self.transport.as_ref()
     }
 
     fn get_base_url(&self) -> &str {
         &self.base_url
     }
 
+    fn get_default_headers(&self) -> Option<&http::HeaderMap> {
+        Some(&self.default_headers)
+    }
+
+    fn get_langcode(&self) -> Option<&str> {
+        self.langcode.as_deref()
+    }
+
+    fn get_langcode_mode(&self) -> LangcodeMode {
+        self.langcode_mode
+    }
+
+    fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn get_retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    fn get_maintenance_gate(&self) -> Option<&MaintenanceGate> {
+        self.maintenance_gate.as_deref()
+    }
+
+    fn get_middlewares(&self) -> &[Arc<dyn Middleware>] {
+        &self.middlewares
+    }
+
+    fn has_auth_strategy(&self) -> bool {
+        self.auth_strategy.is_some()
+    }
+
     async fn before_request(
         &self,
-        req_builder: reqwest::RequestBuilder,
+        parts: &mut http::request::Parts,
         path: &str,
         options: Vec<HttpRequestOption>,
-    ) -> Result<reqwest::RequestBuilder, crate::http_client::ClientError> {
+    ) -> Result<(), DrupalkitError> {
         // Add the X-Consumer-ID header with the client id to each request.
         #[cfg(feature = "consumer")]
-        let req_builder = match &self.client_id {
-            Some(client_id) => req_builder.header(CONSUMER_HEADER_NAME, client_id),
-            None => req_builder,
-        };
+        if let Some(client_id) = &self.client_id {
+            parts.headers.insert(
+                CONSUMER_HEADER_NAME,
+                http::HeaderValue::from_str(client_id)?,
+            );
+        }
 
         // Do nothing if request is anonymous.
         for option in &options {
             if let HttpRequestOption::Anonymous = option {
-                return Ok(req_builder);
+                return Ok(());
             }
         }
 
         // Set auth-info from strategy if set.
-        let auth_strategy = self.auth_strategy.clone();
+        if let Some(auth_strategy) = self.auth_strategy.clone() {
+            let mut rw_auth_strategy = auth_strategy.lock().await;
+            rw_auth_strategy
+                .set_auth_info(parts, path, options, self)
+                .await?;
+        }
+
+        // Requests authenticated by a session cookie need a CSRF token,
+        // unless the method is safe or the token was set explicitly.
+        if !parts.method.is_safe() && !parts.headers.contains_key(CSRF_TOKEN_HEADER_NAME) {
+            if let Some(cookie) = parts.headers.get(http::header::COOKIE).cloned() {
+                let csrf_token = self.csrf_token(&cookie).await?;
+
+                parts.headers.insert(CSRF_TOKEN_HEADER_NAME, csrf_token);
+            }
+        }
 
-        let req_builder = match auth_strategy {
+        Ok(())
+    }
+
+    async fn on_unauthorized(&self, path: &str, options: Vec<HttpRequestOption>) -> bool {
+        // Anonymous requests were not authenticated in the first place.
+        if options
+            .iter()
+            .any(|option| matches!(option, HttpRequestOption::Anonymous))
+        {
+            return false;
+        }
+
+        match &self.auth_strategy {
             Some(auth_strategy) => {
                 let mut rw_auth_strategy = auth_strategy.lock().await;
-                rw_auth_strategy
-                    .set_auth_info(req_builder, path, options, self)
-                    .await?
+                rw_auth_strategy.on_unauthorized(path, options, self).await
             }
-            None => req_builder,
-        };
+            None => false,
+        }
+    }
 
-        Ok(req_builder)
+    async fn on_csrf_token_invalid(&self, _path: &str, _options: Vec<HttpRequestOption>) -> bool {
+        // The session might have changed since the token was fetched.
+        self.csrf_token.clear().await
     }
 
-    async fn after_request(
-        &self,
-        response: reqwest::Response,
-    ) -> Result<reqwest::Response, crate::http_client::ClientError> {
+    async fn after_request(&self, response: Response) -> Result<Response, DrupalkitError> {
         Ok(response)
     }
 }
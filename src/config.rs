@@ -0,0 +1,587 @@
+//! Configuration loaded from environment variables or TOML files.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use drupal_kit::config::DrupalkitConfig;
+//! use drupal_kit::Builder;
+//!
+//! # fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! // Reads `DRUPAL_BASE_URL`, `DRUPAL_AUTH_STRATEGY`, ...
+//! let drupalkit = Builder::from_env("DRUPAL")?.try_build()?;
+//!
+//! // Or from a TOML file.
+//! let config = DrupalkitConfig::from_toml_file("drupal.toml")?;
+//! let drupalkit = Builder::from_config(&config)?.try_build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{error::Error, fmt, path::Path, str::FromStr, time::Duration};
+
+use serde::Deserialize;
+
+use crate::{
+    auth::{BasicAuthStrategy, BearerAuthStrategy, SessionAuthStrategy},
+    cache::MemoryCacheStore,
+    drupalkit_builder::DrupalkitBuilder,
+    language::LangcodeMode,
+    retry::{DEFAULT_INITIAL_BACKOFF, DEFAULT_MAX_BACKOFF},
+    url_builder, RetryPolicy, Secret,
+};
+
+/// Configuration of a `Drupalkit` instance.
+///
+/// # Example
+///
+/// ```toml
+/// base_url = "https://example.com"
+/// consumer_id = "my-consumer"
+/// timeout_secs = 30
+///
+/// [auth]
+/// strategy = "client_credentials"
+/// client_id = "my-consumer"
+/// client_secret = "secret"
+/// scopes = ["content_editor"]
+///
+/// [retry]
+/// max_attempts = 5
+///
+/// [cache]
+/// capacity = 1000
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DrupalkitConfig {
+    pub base_url: String,
+    /// The id sent in the `X-Consumer-ID` header,
+    /// requires the `consumer` feature.
+    pub consumer_id: Option<String>,
+    pub public_files_path: Option<String>,
+    pub user_agent: Option<String>,
+    /// The default langcode of requests, e.g. `de`.
+    pub langcode: Option<String>,
+    /// `path_prefix` or `accept_language`.
+    pub langcode_mode: Option<LangcodeMode>,
+    pub auth: Option<AuthConfig>,
+    pub timeout_secs: Option<u64>,
+    pub retry: Option<RetryConfig>,
+    pub cache: Option<CacheConfig>,
+}
+
+/// The auth strategy and its credentials.
+///
+/// The credentials are never printed by `Debug`,
+/// as configs tend to end up in logs.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case", deny_unknown_fields)]
+pub enum AuthConfig {
+    Basic {
+        username: String,
+        password: Option<Secret>,
+    },
+    Bearer {
+        token: Secret,
+    },
+    Session {
+        username: String,
+        password: Secret,
+    },
+    /// Requires the `simple-oauth` feature.
+    ClientCredentials {
+        client_id: String,
+        client_secret: Secret,
+        #[serde(default)]
+        scopes: Vec<String>,
+    },
+}
+
+/// Settings of the `RetryPolicy`, unset values keep their defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetryConfig {
+    pub max_attempts: Option<u32>,
+    pub initial_backoff_ms: Option<u64>,
+    pub max_backoff_ms: Option<u64>,
+    pub jitter: Option<bool>,
+    pub retry_non_idempotent: Option<bool>,
+}
+
+/// Settings of the in-memory response cache.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    /// The maximum number of cached responses.
+    pub capacity: usize,
+}
+
+/// Error returned when loading a configuration fails.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// A required value is missing, e.g. `DRUPAL_BASE_URL`.
+    Missing(String),
+    /// A value is invalid.
+    Invalid { name: String, message: String },
+    /// The configuration file could not be read.
+    Io(std::io::Error),
+    /// The configuration file is not valid TOML or does
+    /// not match the structure of `DrupalkitConfig`.
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(name) => write!(f, "missing configuration value {}", name),
+            Self::Invalid { name, message } => {
+                write!(f, "invalid configuration value {}: {}", name, message)
+            }
+            Self::Io(err) => write!(f, "could not read configuration: {}", err),
+            Self::Toml(err) => write!(f, "could not parse configuration: {}", err),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Toml(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl ConfigError {
+    fn invalid(name: &str, message: impl fmt::Display) -> Self {
+        Self::Invalid {
+            name: name.to_owned(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl DrupalkitConfig {
+    /// Parses the configuration from a TOML string.
+    pub fn from_toml_str(toml: &str) -> Result<Self, ConfigError> {
+        toml::from_str(toml).map_err(ConfigError::Toml)
+    }
+
+    /// Reads the configuration from a TOML file.
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let toml = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+
+        Self::from_toml_str(&toml)
+    }
+
+    /// Reads the configuration from environment variables
+    /// starting with the given prefix.
+    ///
+    /// With the prefix `DRUPAL` the following variables are read,
+    /// only `DRUPAL_BASE_URL` is required:
+    ///
+    /// - `DRUPAL_BASE_URL`
+    /// - `DRUPAL_CONSUMER_ID`
+    /// - `DRUPAL_PUBLIC_FILES_PATH`
+    /// - `DRUPAL_USER_AGENT`
+    /// - `DRUPAL_LANGCODE` and `DRUPAL_LANGCODE_MODE`,
+    ///   either `path_prefix` or `accept_language`
+    /// - `DRUPAL_AUTH_STRATEGY`, one of `basic`, `bearer`, `session`
+    ///   or `client_credentials`, with its credentials:
+    ///   - `DRUPAL_USERNAME` and `DRUPAL_PASSWORD` for `basic` and `session`
+    ///   - `DRUPAL_TOKEN` for `bearer`
+    ///   - `DRUPAL_CLIENT_ID`, `DRUPAL_CLIENT_SECRET` and `DRUPAL_SCOPES`,
+    ///     separated by commas or spaces, for `client_credentials`
+    /// - `DRUPAL_TIMEOUT_SECS`
+    /// - `DRUPAL_RETRY_MAX_ATTEMPTS`, `DRUPAL_RETRY_INITIAL_BACKOFF_MS`,
+    ///   `DRUPAL_RETRY_MAX_BACKOFF_MS`, `DRUPAL_RETRY_JITTER` and
+    ///   `DRUPAL_RETRY_NON_IDEMPOTENT`, any of them enables retries
+    /// - `DRUPAL_CACHE_CAPACITY`, enables the in-memory response cache
+    pub fn from_env(prefix: &str) -> Result<Self, ConfigError> {
+        Self::from_vars(prefix, |name| std::env::var(name).ok())
+    }
+
+    fn from_vars<F>(prefix: &str, var: F) -> Result<Self, ConfigError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let vars = Vars { prefix, var };
+
+        let auth = match vars.get("AUTH_STRATEGY") {
+            None => None,
+            Some(strategy) => Some(match strategy.as_str() {
+                "basic" => AuthConfig::Basic {
+                    username: vars.require("USERNAME")?,
+                    password: vars.get("PASSWORD").map(Secret::from),
+                },
+                "bearer" => AuthConfig::Bearer {
+                    token: vars.require("TOKEN")?.into(),
+                },
+                "session" => AuthConfig::Session {
+                    username: vars.require("USERNAME")?,
+                    password: vars.require("PASSWORD")?.into(),
+                },
+                "client_credentials" => AuthConfig::ClientCredentials {
+                    client_id: vars.require("CLIENT_ID")?,
+                    client_secret: vars.require("CLIENT_SECRET")?.into(),
+                    scopes: vars
+                        .get("SCOPES")
+                        .map(|scopes| {
+                            scopes
+                                .split([',', ' '])
+                                .filter(|scope| !scope.is_empty())
+                                .map(str::to_owned)
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                },
+                strategy => {
+                    return Err(ConfigError::invalid(
+                        &vars.name("AUTH_STRATEGY"),
+                        format!("unknown auth strategy `{}`", strategy),
+                    ))
+                }
+            }),
+        };
+
+        let retry = RetryConfig {
+            max_attempts: vars.parse("RETRY_MAX_ATTEMPTS")?,
+            initial_backoff_ms: vars.parse("RETRY_INITIAL_BACKOFF_MS")?,
+            max_backoff_ms: vars.parse("RETRY_MAX_BACKOFF_MS")?,
+            jitter: vars.parse("RETRY_JITTER")?,
+            retry_non_idempotent: vars.parse("RETRY_NON_IDEMPOTENT")?,
+        };
+        let has_retry = retry.max_attempts.is_some()
+            || retry.initial_backoff_ms.is_some()
+            || retry.max_backoff_ms.is_some()
+            || retry.jitter.is_some()
+            || retry.retry_non_idempotent.is_some();
+
+        Ok(Self {
+            base_url: vars.require("BASE_URL")?,
+            consumer_id: vars.get("CONSUMER_ID"),
+            public_files_path: vars.get("PUBLIC_FILES_PATH"),
+            user_agent: vars.get("USER_AGENT"),
+            langcode: vars.get("LANGCODE"),
+            langcode_mode: match vars.get("LANGCODE_MODE").as_deref() {
+                None => None,
+                Some("path_prefix") => Some(LangcodeMode::PathPrefix),
+                Some("accept_language") => Some(LangcodeMode::AcceptLanguage),
+                Some(mode) => {
+                    return Err(ConfigError::invalid(
+                        &vars.name("LANGCODE_MODE"),
+                        format!("unknown langcode mode `{}`", mode),
+                    ))
+                }
+            },
+            auth,
+            timeout_secs: vars.parse("TIMEOUT_SECS")?,
+            retry: has_retry.then_some(retry),
+            cache: vars
+                .parse("CACHE_CAPACITY")?
+                .map(|capacity| CacheConfig { capacity }),
+        })
+    }
+}
+
+/// Looks up prefixed variables.
+struct Vars<'a, F> {
+    prefix: &'a str,
+    var: F,
+}
+
+impl<F> Vars<'_, F>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn name(&self, name: &str) -> String {
+        match self.prefix.is_empty() || self.prefix.ends_with('_') {
+            true => format!("{}{}", self.prefix, name),
+            false => format!("{}_{}", self.prefix, name),
+        }
+    }
+
+    /// Returns the value of the variable, treating empty values as unset.
+    fn get(&self, name: &str) -> Option<String> {
+        (self.var)(&self.name(name)).filter(|value| !value.is_empty())
+    }
+
+    fn require(&self, name: &str) -> Result<String, ConfigError> {
+        self.get(name)
+            .ok_or_else(|| ConfigError::Missing(self.name(name)))
+    }
+
+    fn parse<T>(&self, name: &str) -> Result<Option<T>, ConfigError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|err| ConfigError::invalid(&self.name(name), err))
+            })
+            .transpose()
+    }
+}
+
+impl DrupalkitBuilder {
+    /// Create a builder configured by environment variables
+    /// starting with the given prefix.
+    ///
+    /// See `DrupalkitConfig::from_env` for the variables.
+    pub fn from_env(prefix: &str) -> Result<Self, ConfigError> {
+        Self::from_config(&DrupalkitConfig::from_env(prefix)?)
+    }
+
+    /// Create a builder configured by the given config.
+    ///
+    /// Fails if the base url is invalid or the config uses
+    /// a feature that is disabled, e.g. the `client_credentials`
+    /// auth strategy without the `simple-oauth` feature.
+    pub fn from_config(config: &DrupalkitConfig) -> Result<Self, ConfigError> {
+        url_builder::parse_base_url(&config.base_url)
+            .map_err(|err| ConfigError::invalid("base_url", err))?;
+
+        let mut builder = Self::new().set_base_url(&config.base_url);
+
+        if let Some(consumer_id) = &config.consumer_id {
+            #[cfg(feature = "consumer")]
+            {
+                builder = builder.set_client_id(consumer_id);
+            }
+            #[cfg(not(feature = "consumer"))]
+            {
+                let _ = consumer_id;
+
+                return Err(ConfigError::invalid(
+                    "consumer_id",
+                    "requires the `consumer` feature",
+                ));
+            }
+        }
+
+        if let Some(public_files_path) = &config.public_files_path {
+            builder = builder.set_public_files_path(public_files_path);
+        }
+
+        if let Some(user_agent) = &config.user_agent {
+            builder = builder.with_user_agent(user_agent);
+        }
+
+        if let Some(langcode) = &config.langcode {
+            builder = builder.set_langcode(langcode);
+        }
+
+        if let Some(langcode_mode) = config.langcode_mode {
+            builder = builder.set_langcode_mode(langcode_mode);
+        }
+
+        if let Some(timeout_secs) = config.timeout_secs {
+            builder = builder.set_timeout(Duration::from_secs(timeout_secs));
+        }
+
+        if let Some(retry) = &config.retry {
+            let initial_backoff = retry
+                .initial_backoff_ms
+                .map_or(DEFAULT_INITIAL_BACKOFF, Duration::from_millis);
+            let max_backoff = retry
+                .max_backoff_ms
+                .map_or(DEFAULT_MAX_BACKOFF, Duration::from_millis);
+
+            let mut retry_policy = RetryPolicy::new().set_backoff(initial_backoff, max_backoff);
+
+            if let Some(max_attempts) = retry.max_attempts {
+                retry_policy = retry_policy.set_max_attempts(max_attempts);
+            }
+
+            if let Some(jitter) = retry.jitter {
+                retry_policy = retry_policy.set_jitter(jitter);
+            }
+
+            if let Some(retry_non_idempotent) = retry.retry_non_idempotent {
+                retry_policy = retry_policy.set_retry_non_idempotent(retry_non_idempotent);
+            }
+
+            builder = builder.set_retry_policy(retry_policy);
+        }
+
+        if let Some(cache) = &config.cache {
+            builder = builder.set_cache_store(MemoryCacheStore::new(cache.capacity));
+        }
+
+        builder = match &config.auth {
+            None => builder,
+            Some(AuthConfig::Basic { username, password }) => {
+                builder.with_auth_strategy(BasicAuthStrategy::new(username, password.clone()))
+            }
+            Some(AuthConfig::Bearer { token }) => {
+                builder.with_auth_strategy(BearerAuthStrategy::new(token.clone()))
+            }
+            Some(AuthConfig::Session { username, password }) => {
+                builder.with_auth_strategy(SessionAuthStrategy::new(username, password.clone()))
+            }
+            #[cfg(feature = "simple-oauth")]
+            Some(AuthConfig::ClientCredentials {
+                client_id,
+                client_secret,
+                scopes,
+            }) => {
+                builder.with_auth_strategy(crate::simple_oauth::ClientCredentialsAuthStrategy::new(
+                    client_id,
+                    client_secret.clone(),
+                    scopes.clone(),
+                ))
+            }
+            #[cfg(not(feature = "simple-oauth"))]
+            Some(AuthConfig::ClientCredentials { .. }) => {
+                return Err(ConfigError::invalid(
+                    "auth.strategy",
+                    "`client_credentials` requires the `simple-oauth` feature",
+                ))
+            }
+        };
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Result<DrupalkitConfig, ConfigError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        DrupalkitConfig::from_vars("DRUPAL", |name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_from_vars() {
+        let config = from_vars(&[
+            ("DRUPAL_BASE_URL", "https://example.com"),
+            ("DRUPAL_AUTH_STRATEGY", "client_credentials"),
+            ("DRUPAL_CLIENT_ID", "consumer"),
+            ("DRUPAL_CLIENT_SECRET", "secret"),
+            ("DRUPAL_SCOPES", "editor, reviewer"),
+            ("DRUPAL_TIMEOUT_SECS", "30"),
+            ("DRUPAL_RETRY_MAX_ATTEMPTS", "5"),
+            ("DRUPAL_USER_AGENT", ""),
+            ("DRUPAL_LANGCODE_MODE", "accept_language"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.base_url, "https://example.com");
+        assert_eq!(config.timeout_secs, Some(30));
+        assert_eq!(config.retry.unwrap().max_attempts, Some(5));
+        assert!(config.user_agent.is_none());
+        assert_eq!(config.langcode_mode, Some(LangcodeMode::AcceptLanguage));
+        assert!(config.cache.is_none());
+        assert!(matches!(
+            config.auth,
+            Some(AuthConfig::ClientCredentials { scopes, .. }) if scopes == ["editor", "reviewer"]
+        ));
+    }
+
+    #[test]
+    fn test_from_vars_errors() {
+        let err = from_vars(&[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "missing configuration value DRUPAL_BASE_URL"
+        );
+
+        let err = from_vars(&[
+            ("DRUPAL_BASE_URL", "https://example.com"),
+            ("DRUPAL_AUTH_STRATEGY", "session"),
+            ("DRUPAL_USERNAME", "admin"),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "missing configuration value DRUPAL_PASSWORD"
+        );
+
+        let err = from_vars(&[
+            ("DRUPAL_BASE_URL", "https://example.com"),
+            ("DRUPAL_TIMEOUT_SECS", "soon"),
+        ])
+        .unwrap_err();
+        assert!(matches!(err, ConfigError::Invalid { name, .. } if name == "DRUPAL_TIMEOUT_SECS"));
+
+        let err = from_vars(&[
+            ("DRUPAL_BASE_URL", "https://example.com"),
+            ("DRUPAL_AUTH_STRATEGY", "oauth"),
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown auth strategy `oauth`"));
+    }
+
+    #[test]
+    fn test_from_toml_str() {
+        let config = DrupalkitConfig::from_toml_str(
+            r#"
+            base_url = "https://example.com"
+
+            [auth]
+            strategy = "basic"
+            username = "admin"
+            password = "secret"
+
+            [cache]
+            capacity = 100
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.cache.unwrap().capacity, 100);
+        assert!(!format!("{:?}", config.auth).contains("secret"));
+
+        let err = DrupalkitConfig::from_toml_str(
+            r#"
+            base_url = "https://example.com"
+            timeout = 30
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown field `timeout`"));
+    }
+
+    #[test]
+    #[cfg(feature = "reqwest")]
+    fn test_from_config() {
+        let config = DrupalkitConfig {
+            base_url: "https://example.com".to_owned(),
+            timeout_secs: Some(10),
+            retry: Some(RetryConfig {
+                max_attempts: Some(4),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let dk = DrupalkitBuilder::from_config(&config)
+            .unwrap()
+            .try_build()
+            .unwrap();
+
+        assert_eq!(dk.timeout, Some(Duration::from_secs(10)));
+        assert_eq!(dk.retry_policy.unwrap().max_attempts(), 4);
+
+        let err = DrupalkitBuilder::from_config(&DrupalkitConfig {
+            base_url: "example.com".to_owned(),
+            ..Default::default()
+        })
+        .err()
+        .unwrap();
+        assert!(matches!(err, ConfigError::Invalid { name, .. } if name == "base_url"));
+    }
+}
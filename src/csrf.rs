@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use http::{header, HeaderValue, Method};
+use tokio::sync::Mutex;
+
+use crate::{
+    error::FailedRequestError,
+    http_client::{HttpClient, HttpRequestOption},
+    transport::ResponseExt,
+    Drupalkit, DrupalkitError,
+};
+
+pub(crate) const CSRF_TOKEN_HEADER_NAME: &str = "x-csrf-token";
+
+/// The CSRF token of a session, shared by all clones of a `Drupalkit`
+/// using the same auth strategy.
+#[derive(Debug, Default)]
+pub(crate) struct CsrfToken {
+    /// The `Cookie` header of the session and its token.
+    token: Mutex<Option<(HeaderValue, HeaderValue)>>,
+}
+
+impl CsrfToken {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Caches the token of the session identified by the given
+    /// `Cookie` header, e.g. the one returned by the login.
+    pub(crate) async fn set(&self, cookie: HeaderValue, token: HeaderValue) {
+        *self.token.lock().await = Some((cookie, token));
+    }
+
+    /// Drops the token, returns whether there was one.
+    pub(crate) async fn clear(&self) -> bool {
+        self.token.lock().await.take().is_some()
+    }
+}
+
+impl Drupalkit {
+    /// Returns the CSRF token of the session identified by the
+    /// given `Cookie` header, fetching it from `/session/token`
+    /// unless cached, e.g. by the login of `SessionAuthStrategy`.
+    pub(crate) async fn csrf_token(
+        &self,
+        cookie: &HeaderValue,
+    ) -> Result<HeaderValue, DrupalkitError> {
+        // Keep the lock while fetching, so concurrent
+        // requests fetch the token only once.
+        let mut token = self.csrf_token.token.lock().await;
+
+        if let Some((token_cookie, token)) = token.as_ref() {
+            if token_cookie == cookie {
+                return Ok(token.clone());
+            }
+        }
+
+        // This MUST be an anonymous request, the auth info
+        // is added by the request being prepared already.
+        let response = self
+            .request(
+                Method::GET,
+                "/session/token",
+                "",
+                vec![
+                    HttpRequestOption::Anonymous,
+                    HttpRequestOption::NoCache,
+                    HttpRequestOption::Header(header::COOKIE, cookie.clone()),
+                ],
+            )
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(FailedRequestError::from_response(response).await?.into());
+        }
+
+        let mut value = HeaderValue::from_str(response.text().await?.trim())?;
+        value.set_sensitive(true);
+
+        *token = Some((cookie.clone(), value.clone()));
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,258 @@
+use std::sync::Arc;
+
+use futures_util::StreamExt;
+use http::{header, HeaderValue, Method, StatusCode};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    error::FailedRequestError,
+    http_client::{HttpClient, HttpRequestOption},
+    transport::ResponseExt,
+    Drupalkit, DrupalkitError,
+};
+
+/// Progress of a file download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Number of bytes written, including the bytes
+    /// of a resumed download.
+    pub downloaded: u64,
+    /// Size of the file, if known.
+    pub total: Option<u64>,
+}
+
+/// Options for `Drupalkit::download_file`.
+#[derive(Clone)]
+pub struct DownloadOptions {
+    resume_from: u64,
+    max_resumes: u32,
+    on_progress: Option<Arc<dyn Fn(DownloadProgress) + Send + Sync>>,
+    request_options: Vec<HttpRequestOption>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            resume_from: 0,
+            max_resumes: 3,
+            on_progress: None,
+            request_options: Vec::new(),
+        }
+    }
+}
+
+impl DownloadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resumes a download, of which the given number of bytes
+    /// were already written, e.g. to a partially downloaded file.
+    pub fn set_resume_from(mut self, resume_from: u64) -> Self {
+        self.resume_from = resume_from;
+
+        self
+    }
+
+    /// Sets how often an interrupted download is resumed
+    /// using a `Range` request before giving up.
+    pub fn set_max_resumes(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = max_resumes;
+
+        self
+    }
+
+    /// Sets a callback invoked after each chunk written.
+    pub fn set_on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: Fn(DownloadProgress) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(on_progress));
+
+        self
+    }
+
+    /// Sets additional options for the download requests.
+    pub fn set_request_options(mut self, request_options: Vec<HttpRequestOption>) -> Self {
+        self.request_options = request_options;
+
+        self
+    }
+}
+
+impl Drupalkit {
+    /// Downloads a file and writes it to the given writer.
+    ///
+    /// The file can be given as a Drupal stream wrapper URI
+    /// (`public://` or `private://`), a path relative to the base url
+    /// or an absolute URL. Requests to the Drupal site are authenticated
+    /// with the configured auth strategy, which is needed for private files.
+    /// Requests to other hosts are always anonymous.
+    ///
+    /// Interrupted downloads are resumed using `Range` requests.
+    /// If the server does not support ranges, the already written
+    /// bytes are skipped instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "reqwest")] {
+    /// use drupal_kit::{download::DownloadOptions, Drupalkit};
+    ///
+    /// # async fn run(drupalkit: Drupalkit) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = tokio::fs::File::create("report.pdf").await?;
+    ///
+    /// let options = DownloadOptions::new().set_on_progress(|progress| {
+    ///     println!("{} / {:?} bytes", progress.downloaded, progress.total);
+    /// });
+    ///
+    /// drupalkit
+    ///     .download_file("private://reports/report.pdf", &mut file, options)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub async fn download_file<W>(
+        &self,
+        uri_or_url: &str,
+        writer: &mut W,
+        options: DownloadOptions,
+    ) -> Result<DownloadProgress, DrupalkitError>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
+        let path = self.file_path(uri_or_url);
+
+        let mut progress = DownloadProgress {
+            downloaded: options.resume_from,
+            total: None,
+        };
+        let mut resumes = 0;
+
+        loop {
+            let mut request_options = options.request_options.clone();
+
+            if progress.downloaded > 0 {
+                request_options.push(HttpRequestOption::Header(
+                    header::RANGE,
+                    HeaderValue::from_str(&format!("bytes={}-", progress.downloaded))?,
+                ));
+            }
+
+            // Public files are served by the web server
+            // directly, without a language prefix.
+            if uri_or_url.starts_with("public://") {
+                request_options.push(HttpRequestOption::Langcode(String::new()));
+            }
+
+            // Never send credentials to other hosts.
+            if !self.is_same_origin(&path) {
+                request_options.push(HttpRequestOption::Anonymous);
+            }
+
+            let response = self
+                .request(Method::GET, &path, "", request_options)
+                .await?;
+
+            // The file was already downloaded completely.
+            if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && progress.downloaded > 0 {
+                return Ok(progress);
+            }
+
+            if !response.status().is_success() {
+                return Err(FailedRequestError::from_response(response).await?.into());
+            }
+
+            // Skip already written bytes if the range was ignored.
+            let mut skip = 0;
+
+            if response.status() == StatusCode::PARTIAL_CONTENT {
+                progress.total = content_range_total(response.headers());
+            } else {
+                skip = progress.downloaded;
+                progress.total = response.content_length();
+            }
+
+            let mut stream = response.bytes_stream();
+            let mut interrupted = false;
+
+            while let Some(chunk) = stream.next().await {
+                let mut chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(_) if resumes < options.max_resumes => {
+                        // Connection dropped, resume with a range request.
+                        interrupted = true;
+                        break;
+                    }
+                    Err(err) => return Err(err),
+                };
+
+                if skip > 0 {
+                    let skipped = skip.min(chunk.len() as u64);
+                    skip -= skipped;
+                    chunk = chunk.slice(skipped as usize..);
+                }
+
+                if chunk.is_empty() {
+                    continue;
+                }
+
+                writer.write_all(&chunk).await?;
+
+                progress.downloaded += chunk.len() as u64;
+
+                if let Some(on_progress) = &options.on_progress {
+                    on_progress(progress);
+                }
+            }
+
+            if !interrupted {
+                writer.flush().await?;
+
+                return Ok(progress);
+            }
+
+            resumes += 1;
+        }
+    }
+
+    /// Resolves a Drupal stream wrapper URI to a path.
+    fn file_path(&self, uri_or_url: &str) -> String {
+        if let Some(target) = uri_or_url.strip_prefix("public://") {
+            format!(
+                "{}/{}",
+                self.public_files_path.trim_end_matches('/'),
+                target
+            )
+        } else if let Some(target) = uri_or_url.strip_prefix("private://") {
+            format!("/system/files/{}", target)
+        } else {
+            uri_or_url.to_owned()
+        }
+    }
+
+    /// Checks whether the given path or URL points to the Drupal site.
+    fn is_same_origin(&self, path: &str) -> bool {
+        let Ok(url) = url::Url::parse(path) else {
+            // Relative paths always point to the Drupal site.
+            return true;
+        };
+
+        url::Url::parse(&self.base_url)
+            .map(|base_url| base_url.origin() == url.origin())
+            .unwrap_or(false)
+    }
+}
+
+/// Returns the complete length from a `Content-Range: bytes 0-99/1234` header.
+fn content_range_total(headers: &http::HeaderMap) -> Option<u64> {
+    headers
+        .get(header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit_once('/')?
+        .1
+        .parse()
+        .ok()
+}
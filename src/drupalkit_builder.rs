@@ -1,22 +1,56 @@
-use crate::Drupalkit;
+use std::{sync::Arc, time::Duration};
+
+use http::{header, HeaderMap, HeaderValue};
+use tokio::sync::Mutex;
+
+use crate::{
+    auth::AuthStrategy,
+    cache::{CacheMiddleware, CacheStore},
+    client::DEFAULT_PUBLIC_FILES_PATH,
+    csrf::CsrfToken,
+    language::LangcodeMode,
+    middleware::Middleware,
+    transport::Transport,
+    url_builder, BuildError, Drupalkit, MaintenanceGate, MaintenancePolicy, RateLimit, RateLimiter,
+    RetryPolicy,
+};
+
+#[cfg(feature = "tower")]
+use crate::service::{into_drupalkit_error, DrupalRequest, DrupalService, RequestService};
+#[cfg(feature = "tower")]
+use crate::transport::Response;
+#[cfg(feature = "tower")]
+use tower::{util::BoxCloneSyncService, Layer, Service, ServiceExt};
 
 #[derive(Default)]
 pub struct DrupalkitBuilder {
+    #[cfg(feature = "reqwest")]
     http_client_builder: reqwest::ClientBuilder,
+    transport: Option<Arc<dyn Transport>>,
 
     base_url: Option<String>,
+    public_files_path: Option<String>,
     #[cfg(feature = "consumer")]
     client_id: Option<String>,
+    auth_strategy: Option<Arc<Mutex<dyn AuthStrategy>>>,
+    default_headers: HeaderMap,
+    user_agent: Option<String>,
+    langcode: Option<String>,
+    langcode_mode: LangcodeMode,
+    timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit: Option<RateLimit>,
+    path_rate_limits: Vec<(String, RateLimit)>,
+    maintenance_policy: Option<MaintenancePolicy>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    cache_store: Option<Arc<dyn CacheStore>>,
+    #[cfg(feature = "tower")]
+    layers: Vec<Box<dyn FnOnce(DrupalService) -> DrupalService + Send>>,
 }
 
 impl DrupalkitBuilder {
     pub fn new() -> Self {
-        let http_client_builder = reqwest::Client::builder();
-
-        Self {
-            http_client_builder,
-            ..Default::default()
-        }
+        Self::default()
     }
 
     pub fn set_base_url(mut self, base_url: &str) -> Self {
@@ -25,6 +59,16 @@ impl DrupalkitBuilder {
         self
     }
 
+    /// Set the path public files are served from,
+    /// used to resolve `public://` URIs.
+    ///
+    /// Defaults to `/sites/default/files`.
+    pub fn set_public_files_path(mut self, public_files_path: &str) -> Self {
+        self.public_files_path = Some(public_files_path.to_owned());
+
+        self
+    }
+
     #[cfg(feature = "consumer")]
     pub fn set_client_id(mut self, client_id: &str) -> Self {
         self.client_id = Some(client_id.to_owned());
@@ -32,6 +76,244 @@ impl DrupalkitBuilder {
         self
     }
 
+    /// Authenticate requests using the given auth strategy.
+    ///
+    /// The same as calling `Drupalkit::set_auth_strategy`
+    /// on the built instance.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "reqwest")] {
+    /// use drupal_kit::auth::BasicAuthStrategy;
+    /// use drupal_kit::Builder;
+    ///
+    /// let drupalkit = Builder::new()
+    ///     .set_base_url("https://example.com")
+    ///     .with_auth_strategy(BasicAuthStrategy::new("admin", Some("secret")))
+    ///     .with_user_agent("my-app/1.0")
+    ///     .try_build()
+    ///     .expect("drupalkit must be valid");
+    /// # }
+    /// ```
+    pub fn with_auth_strategy<T>(mut self, auth_strategy: T) -> Self
+    where
+        T: AuthStrategy + 'static,
+    {
+        self.auth_strategy = Some(Arc::new(Mutex::new(auth_strategy)));
+
+        self
+    }
+
+    /// Add the given headers to every request.
+    ///
+    /// Headers set per request using `HttpRequestOption::Header`
+    /// or by the auth strategy take precedence.
+    pub fn with_default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+
+        self
+    }
+
+    /// Send the given `User-Agent` header with every request.
+    ///
+    /// Takes precedence over a `User-Agent` set with `with_default_headers`.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_owned());
+
+        self
+    }
+
+    /// Request content in the given language by default,
+    /// e.g. `de` on a multilingual site.
+    ///
+    /// It can be overridden per request using `HttpRequestOption::Langcode`.
+    pub fn set_langcode(mut self, langcode: &str) -> Self {
+        self.langcode = Some(langcode.to_owned());
+
+        self
+    }
+
+    /// Set how the langcode of a request is sent to Drupal.
+    ///
+    /// Defaults to `LangcodeMode::PathPrefix`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "reqwest")] {
+    /// use drupal_kit::language::LangcodeMode;
+    /// use drupal_kit::Builder;
+    ///
+    /// let drupalkit = Builder::new()
+    ///     .set_base_url("https://example.com")
+    ///     .set_langcode("de")
+    ///     .set_langcode_mode(LangcodeMode::AcceptLanguage)
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn set_langcode_mode(mut self, langcode_mode: LangcodeMode) -> Self {
+        self.langcode_mode = langcode_mode;
+
+        self
+    }
+
+    /// Set the default timeout for requests.
+    ///
+    /// The timeout covers the whole request including retries,
+    /// for `request_json` also reading the response body.
+    /// It can be overridden per request using `HttpRequestOption::Timeout`.
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Retry failed requests according to the given policy.
+    ///
+    /// The policy can be overridden per request using
+    /// `HttpRequestOption::Retry` and `HttpRequestOption::NoRetry`.
+    pub fn set_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+
+        self
+    }
+
+    /// Limit the rate of all requests.
+    ///
+    /// The limit is shared by all clones of the built `Drupalkit`.
+    /// Requests exceeding the limit wait until they may be sent.
+    /// Every retry takes from the limit as well, while responses
+    /// served from the cache do not.
+    pub fn set_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+
+        self
+    }
+
+    /// Limit the rate of requests to paths starting with the given prefix,
+    /// e.g. `/jsonapi` or `/oauth/token`. The prefix matches whole path
+    /// segments only, so `/user` does not limit requests to `/users`.
+    ///
+    /// Only the limit with the longest matching prefix applies
+    /// to a request, in addition to the global rate limit.
+    pub fn set_path_rate_limit(mut self, prefix: &str, rate_limit: RateLimit) -> Self {
+        self.path_rate_limits.push((prefix.to_owned(), rate_limit));
+
+        self
+    }
+
+    /// Pause all requests while the site is in maintenance mode,
+    /// until it is back online or the maximum wait of the policy elapsed.
+    ///
+    /// The pause is shared by all clones of the built `Drupalkit`.
+    /// Without a policy, maintenance mode responses are returned
+    /// and fail with `DrupalkitError::Maintenance`.
+    pub fn set_maintenance_policy(mut self, maintenance_policy: MaintenancePolicy) -> Self {
+        self.maintenance_policy = Some(maintenance_policy);
+
+        self
+    }
+
+    /// Add a middleware to the end of the middleware chain.
+    ///
+    /// Middlewares are invoked in the order they were added.
+    pub fn with_middleware<T>(mut self, middleware: T) -> Self
+    where
+        T: Middleware,
+    {
+        self.middlewares.push(Arc::new(middleware));
+
+        self
+    }
+
+    /// Cache responses of anonymous `GET` requests in the given store.
+    ///
+    /// Responses are cached according to their `Cache-Control` header
+    /// and revalidated using `ETag` and `Last-Modified` once stale.
+    /// Requests with an `Authorization` or `Cookie` header are never cached,
+    /// so responses for one user are never served to another.
+    /// The cache can be bypassed per request using `HttpRequestOption::NoCache`.
+    ///
+    /// The cache is the innermost middleware and is shared
+    /// by all clones of the built `Drupalkit`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "reqwest")] {
+    /// use drupal_kit::{cache::MemoryCacheStore, Builder};
+    ///
+    /// let drupalkit = Builder::new()
+    ///     .set_base_url("https://example.com")
+    ///     .set_cache_store(MemoryCacheStore::new(1000))
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn set_cache_store<T>(mut self, cache_store: T) -> Self
+    where
+        T: CacheStore,
+    {
+        self.cache_store = Some(Arc::new(cache_store));
+
+        self
+    }
+
+    /// Add a `tower::Layer` wrapping the request flow.
+    ///
+    /// Every request, including those made with `HttpClient::request`,
+    /// is passed through the layers, running `before_request`, the
+    /// middleware chain and `after_request` inside them.
+    /// The first added layer is the outermost one.
+    ///
+    /// Errors of a layer are converted into `DrupalkitError::Middleware`,
+    /// unless they already are a `DrupalkitError`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use drupal_kit::Builder;
+    /// use tower::limit::ConcurrencyLimitLayer;
+    ///
+    /// let drupalkit = Builder::new()
+    ///     .set_base_url("https://example.com")
+    ///     .with_layer(ConcurrencyLimitLayer::new(8))
+    ///     .build();
+    /// ```
+    #[cfg(feature = "tower")]
+    pub fn with_layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<DrupalService> + Send + 'static,
+        L::Service: Service<DrupalRequest, Response = Response> + Clone + Send + Sync + 'static,
+        <L::Service as Service<DrupalRequest>>::Error:
+            Into<Box<dyn std::error::Error + Send + Sync>>,
+        <L::Service as Service<DrupalRequest>>::Future: Send + 'static,
+    {
+        self.layers.push(Box::new(move |service| {
+            let service = layer
+                .layer(service)
+                .map_err(|err| into_drupalkit_error(err.into()));
+
+            BoxCloneSyncService::new(service)
+        }));
+
+        self
+    }
+
+    /// Send requests using the given transport instead of `reqwest`.
+    ///
+    /// Settings made with `build_http_client` are ignored
+    /// if a transport is set.
+    pub fn set_transport<T>(mut self, transport: T) -> Self
+    where
+        T: Transport,
+    {
+        self.transport = Some(Arc::new(transport));
+
+        self
+    }
+
     /// Customize the internal `reqwest::Client` instance using the `reqwest::ClientBuilder`.
     ///
     /// # Example: Allow insecure SSL
@@ -49,6 +331,7 @@ impl DrupalkitBuilder {
     ///     )
     ///     .build();
     /// ```
+    #[cfg(feature = "reqwest")]
     pub fn build_http_client<F>(mut self, f: F) -> Self
     where
         F: Fn(reqwest::ClientBuilder) -> reqwest::ClientBuilder,
@@ -58,16 +341,127 @@ impl DrupalkitBuilder {
         self
     }
 
+    /// Build the `Drupalkit` instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `try_build` fails.
     pub fn build(self) -> Drupalkit {
-        Drupalkit {
-            http_client: self.http_client_builder.build().unwrap(),
+        self.try_build().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Build the `Drupalkit` instance.
+    ///
+    /// Fails if the base url is not set or is not a valid
+    /// http or https URL with a host, if no transport is set
+    /// and the `reqwest` feature is disabled, or if the user agent
+    /// is not a valid header value.
+    pub fn try_build(self) -> Result<Drupalkit, BuildError> {
+        let base_url = self.base_url.ok_or(BuildError::MissingBaseUrl)?;
+
+        url_builder::parse_base_url(&base_url)?;
+
+        let mut default_headers = self.default_headers;
+
+        if let Some(user_agent) = &self.user_agent {
+            default_headers.insert(
+                header::USER_AGENT,
+                HeaderValue::from_str(user_agent).map_err(BuildError::InvalidHeader)?,
+            );
+        }
+
+        let rate_limiter = match (self.rate_limit, self.path_rate_limits.is_empty()) {
+            (None, true) => None,
+            (global, _) => Some(Arc::new(RateLimiter::new(global, self.path_rate_limits))),
+        };
+
+        let maintenance_gate = match self.maintenance_policy {
+            Some(policy) => {
+                let probe_uri = url_builder::build_url(&base_url, policy.probe_path(), &[])
+                    .ok()
+                    .and_then(|url| url.as_str().parse().ok())
+                    .ok_or_else(|| {
+                        BuildError::InvalidBaseUrl(format!(
+                            "invalid maintenance probe path: {}",
+                            policy.probe_path()
+                        ))
+                    })?;
 
-            base_url: self.base_url.expect("base_url must be set for drupalkit"),
+                Some(Arc::new(MaintenanceGate::new(
+                    policy,
+                    probe_uri,
+                    default_headers.clone(),
+                )))
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "reqwest")]
+        let transport: Arc<dyn Transport> = match self.transport {
+            Some(transport) => transport,
+            None => {
+                let client = self
+                    .http_client_builder
+                    .build()
+                    .map_err(BuildError::HttpClient)?;
+
+                Arc::new(crate::transport::ReqwestTransport::new(client))
+            }
+        };
+        #[cfg(not(feature = "reqwest"))]
+        let transport = self.transport.ok_or(BuildError::MissingTransport)?;
+
+        let mut middlewares = self.middlewares;
+
+        if let Some(cache_store) = &self.cache_store {
+            middlewares.push(Arc::new(CacheMiddleware::new(cache_store.clone())));
+        }
+
+        // Apply the layers in reverse order, so the first
+        // added layer becomes the outermost one.
+        #[cfg(feature = "tower")]
+        let service = match self.layers.is_empty() {
+            true => None,
+            false => Some(self.layers.into_iter().rev().fold(
+                BoxCloneSyncService::new(RequestService),
+                |service: DrupalService, layer| layer(service),
+            )),
+        };
+
+        Ok(Drupalkit {
+            transport,
+
+            base_url,
+            public_files_path: self
+                .public_files_path
+                .unwrap_or_else(|| DEFAULT_PUBLIC_FILES_PATH.to_owned()),
             #[cfg(feature = "consumer")]
             client_id: self.client_id,
 
-            auth_strategy: None,
-        }
+            auth_strategy: self.auth_strategy,
+            csrf_token: CsrfToken::new(),
+            default_headers,
+            langcode: self.langcode,
+            langcode_mode: self.langcode_mode,
+            timeout: self.timeout,
+            retry_policy: self.retry_policy,
+            rate_limiter,
+            maintenance_gate,
+            middlewares,
+            cache_store: self.cache_store,
+            #[cfg(feature = "tower")]
+            service,
+        })
+    }
+
+    /// Build a synchronous `blocking::Drupalkit` instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics for the same reasons as `build`.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> crate::blocking::Drupalkit {
+        self.build().into()
     }
 }
 
@@ -78,6 +472,7 @@ mod tests {
     const BASE_URL: &str = "https://example.com";
 
     #[test]
+    #[cfg(feature = "reqwest")]
     fn test_build_minimal() {
         let dk = DrupalkitBuilder::new().set_base_url(BASE_URL).build();
 
@@ -88,6 +483,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "reqwest")]
     fn test_build_max() {
         #[cfg(feature = "consumer")]
         let client_id = "_client-id_";
@@ -106,4 +502,39 @@ mod tests {
         #[cfg(feature = "consumer")]
         assert_eq!(dk.client_id, Some(client_id.to_owned()));
     }
+
+    #[test]
+    fn test_try_build_errors() {
+        assert!(matches!(
+            DrupalkitBuilder::new().try_build(),
+            Err(BuildError::MissingBaseUrl)
+        ));
+        assert!(matches!(
+            DrupalkitBuilder::new()
+                .set_base_url("example.com")
+                .try_build(),
+            Err(BuildError::InvalidBaseUrl(_))
+        ));
+        assert!(matches!(
+            DrupalkitBuilder::new()
+                .set_base_url("file:///var/www")
+                .try_build(),
+            Err(BuildError::InvalidBaseUrl(_))
+        ));
+        assert!(matches!(
+            DrupalkitBuilder::new()
+                .set_base_url(BASE_URL)
+                .with_user_agent("my-app\n")
+                .try_build(),
+            Err(BuildError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "base url must use the http or https scheme")]
+    fn test_build_invalid_base_url() {
+        DrupalkitBuilder::new()
+            .set_base_url("ftp://example.com")
+            .build();
+    }
 }
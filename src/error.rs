@@ -0,0 +1,577 @@
+use core::fmt;
+use std::error::Error;
+
+use bytes::Bytes;
+use http::{header::InvalidHeaderValue, HeaderMap, StatusCode};
+use serde::{Deserialize, Deserializer};
+
+use crate::{auth::AuthStrategyError, retry::RETRYABLE_STATUSES, transport::Response};
+
+/// Error returned by all fallible drupalkit operations.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DrupalkitError {
+    /// The request could not be sent or the response could not be received.
+    Transport(TransportError),
+    /// The server responded with a non-success status code.
+    Status(FailedRequestError),
+    /// The site is in maintenance mode and answered
+    /// with `503 Service Unavailable`.
+    Maintenance(FailedRequestError),
+    /// The response body could not be deserialized.
+    Decode {
+        /// The raw response body.
+        body: Bytes,
+        /// Path to the value that failed to deserialize, e.g. `data[0].id`.
+        path: String,
+        /// The error of `serde_json`.
+        source: serde_json::Error,
+    },
+    /// The request body could not be serialized.
+    Encode(serde_json::Error),
+    /// Writing a downloaded file failed.
+    Io(std::io::Error),
+    /// The auth strategy could not set auth info for the request.
+    Auth(AuthStrategyError),
+    /// The client or request is misconfigured.
+    Config(String),
+    /// The request did not complete within the configured timeout.
+    Timeout,
+    /// The request was aborted using a cancellation token.
+    Cancelled,
+    /// A middleware or `tower` layer failed.
+    Middleware(Box<dyn Error + Send + Sync>),
+}
+
+impl DrupalkitError {
+    /// Checks whether the failed request may succeed when sent again.
+    ///
+    /// This is the case for timeouts, connection errors and the
+    /// status codes 408, 429, 502, 503 and 504.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(err) => matches!(
+                err.kind(),
+                TransportErrorKind::Timeout | TransportErrorKind::Connect
+            ),
+            Self::Maintenance(_) => true,
+            Self::Status(err) => RETRYABLE_STATUSES.contains(&err.status()),
+            _ => false,
+        }
+    }
+
+    /// Checks whether the request timed out, either because of the
+    /// request timeout or a timeout of the underlying HTTP client.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::Timeout => true,
+            Self::Transport(err) => err.kind() == TransportErrorKind::Timeout,
+            _ => false,
+        }
+    }
+
+    /// Returns the HTTP status code of the response, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Status(err) | Self::Maintenance(err) => Some(err.status()),
+            _ => None,
+        }
+    }
+
+    /// Deserializes the given bytes as JSON, keeping track of the
+    /// path to the value that failed to deserialize.
+    pub(crate) fn decode<T>(body: Bytes) -> Result<T, Self>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut deserializer = serde_json::Deserializer::from_slice(&body);
+
+        match serde_path_to_error::deserialize(&mut deserializer) {
+            Ok(payload) => Ok(payload),
+            Err(err) => {
+                let path = err.path().to_string();
+
+                Err(Self::Decode {
+                    body,
+                    path,
+                    source: err.into_inner(),
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for DrupalkitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(err) => write!(f, "http transport error: {}", err),
+            Self::Status(err) => err.fmt(f),
+            Self::Maintenance(err) => write!(f, "site is in maintenance mode, {}", err),
+            Self::Decode { path, source, .. } => {
+                write!(
+                    f,
+                    "could not decode response body at `{}`: {}",
+                    path, source
+                )
+            }
+            Self::Encode(err) => write!(f, "could not encode request body: {}", err),
+            Self::Io(err) => write!(f, "io error: {}", err),
+            Self::Auth(err) => err.fmt(f),
+            Self::Config(message) => write!(f, "invalid configuration: {}", message),
+            Self::Timeout => write!(f, "request timed out"),
+            Self::Cancelled => write!(f, "request was cancelled"),
+            Self::Middleware(err) => write!(f, "middleware error: {}", err),
+        }
+    }
+}
+
+impl Error for DrupalkitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Transport(err) => Some(err),
+            Self::Decode { source, .. } => Some(source),
+            Self::Encode(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::Auth(err) => Some(err),
+            Self::Middleware(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransportError> for DrupalkitError {
+    fn from(value: TransportError) -> Self {
+        Self::Transport(value)
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for DrupalkitError {
+    fn from(value: reqwest::Error) -> Self {
+        Self::Transport(value.into())
+    }
+}
+
+impl From<http::Error> for DrupalkitError {
+    fn from(value: http::Error) -> Self {
+        Self::Config(format!("invalid request: {}", value))
+    }
+}
+
+impl From<FailedRequestError> for DrupalkitError {
+    fn from(value: FailedRequestError) -> Self {
+        match value.is_maintenance() {
+            true => Self::Maintenance(value),
+            false => Self::Status(value),
+        }
+    }
+}
+
+impl From<AuthStrategyError> for DrupalkitError {
+    fn from(value: AuthStrategyError) -> Self {
+        Self::Auth(value)
+    }
+}
+
+impl From<std::io::Error> for DrupalkitError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<url::ParseError> for DrupalkitError {
+    fn from(value: url::ParseError) -> Self {
+        Self::Config(format!("invalid url: {}", value))
+    }
+}
+
+impl From<InvalidHeaderValue> for DrupalkitError {
+    fn from(value: InvalidHeaderValue) -> Self {
+        Self::Config(value.to_string())
+    }
+}
+
+/// Error returned by `DrupalkitBuilder::try_build`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// No base url was set.
+    MissingBaseUrl,
+    /// The base url is not a valid http or https URL with a host.
+    InvalidBaseUrl(String),
+    /// No transport was set and the `reqwest` feature is disabled.
+    MissingTransport,
+    /// The value of a default header, e.g. the user agent, is invalid.
+    InvalidHeader(InvalidHeaderValue),
+    /// The `reqwest::Client` could not be built.
+    #[cfg(feature = "reqwest")]
+    HttpClient(reqwest::Error),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingBaseUrl => write!(f, "base_url must be set for drupalkit"),
+            Self::InvalidBaseUrl(message) => message.fmt(f),
+            Self::MissingTransport => write!(f, "transport must be set for drupalkit"),
+            Self::InvalidHeader(err) => write!(f, "invalid default header: {}", err),
+            #[cfg(feature = "reqwest")]
+            Self::HttpClient(err) => write!(f, "could not build http client: {}", err),
+        }
+    }
+}
+
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidHeader(err) => Some(err),
+            #[cfg(feature = "reqwest")]
+            Self::HttpClient(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<BuildError> for DrupalkitError {
+    fn from(value: BuildError) -> Self {
+        Self::Config(value.to_string())
+    }
+}
+
+/// The kind of a `TransportError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransportErrorKind {
+    /// The connection to the server could not be established.
+    Connect,
+    /// The transport timed out.
+    Timeout,
+    /// Reading or writing a body failed.
+    Body,
+    /// Any other error, e.g. an invalid request or redirect loop.
+    Other,
+}
+
+/// An error of a `Transport`, wrapping the error of the underlying HTTP client.
+#[derive(Debug)]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl TransportError {
+    pub fn new(kind: TransportErrorKind, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    pub fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+
+    /// Converts the error of a body, keeping the kind
+    /// of errors that are a `TransportError` already.
+    pub(crate) fn from_boxed(err: Box<dyn Error + Send + Sync>) -> Self {
+        let err = match err.downcast::<Self>() {
+            Ok(err) => return *err,
+            Err(err) => err,
+        };
+
+        #[cfg(feature = "reqwest")]
+        let err = match err.downcast::<reqwest::Error>() {
+            Ok(err) => return (*err).into(),
+            Err(err) => err,
+        };
+
+        Self::new(TransportErrorKind::Body, err)
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for TransportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for TransportError {
+    fn from(value: reqwest::Error) -> Self {
+        let kind = if value.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if value.is_connect() {
+            TransportErrorKind::Connect
+        } else if value.is_body() || value.is_decode() {
+            TransportErrorKind::Body
+        } else {
+            TransportErrorKind::Other
+        };
+
+        Self::new(kind, value)
+    }
+}
+
+/// A response with a non-success status code.
+///
+/// The response body is buffered, so it can be inspected
+/// after the response has been consumed.
+#[derive(Debug, Clone)]
+pub struct FailedRequestError {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl FailedRequestError {
+    /// Buffers the body of the given response.
+    pub async fn from_response(response: Response) -> Result<Self, DrupalkitError> {
+        let (parts, body) = response.into_parts();
+        let status = parts.status;
+        let headers = parts.headers;
+        let body = body.bytes().await?;
+
+        Ok(Self::new(status, headers, body))
+    }
+
+    pub(crate) fn new(status: StatusCode, headers: HeaderMap, body: Bytes) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Returns the errors of a JSON:API error response.
+    ///
+    /// The returned list is empty if the body is not a JSON:API error document.
+    pub fn jsonapi_errors(&self) -> Vec<JsonApiError> {
+        #[derive(Deserialize)]
+        struct Document {
+            errors: Vec<JsonApiError>,
+        }
+
+        serde_json::from_slice::<Document>(&self.body)
+            .map(|document| document.errors)
+            .unwrap_or_default()
+    }
+
+    /// Returns the message of a core REST error response,
+    /// e.g. `{"message": "No route found."}`.
+    pub fn drupal_message(&self) -> Option<String> {
+        #[derive(Deserialize)]
+        struct Document {
+            message: String,
+        }
+
+        serde_json::from_slice::<Document>(&self.body)
+            .map(|document| document.message)
+            .ok()
+    }
+
+    /// Checks whether the response is Drupal's maintenance mode response.
+    ///
+    /// This is a `503 Service Unavailable` response with either the
+    /// maintenance page, titled `Site under maintenance`, or Drupal's
+    /// maintenance message, e.g. `Drupal is currently under maintenance.`,
+    /// in the JSON or JSON:API error document returned for requests with a format.
+    pub fn is_maintenance(&self) -> bool {
+        const TITLE: &[u8] = b"<title>Site under maintenance";
+        const MESSAGE: &str = "currently under maintenance";
+
+        if self.status != StatusCode::SERVICE_UNAVAILABLE {
+            return false;
+        }
+
+        self.body.windows(TITLE.len()).any(|window| window == TITLE)
+            || self
+                .jsonapi_errors()
+                .into_iter()
+                .filter_map(|error| error.detail)
+                .any(|detail| detail.contains(MESSAGE))
+            || self
+                .drupal_message()
+                .is_some_and(|message| message.contains(MESSAGE))
+    }
+
+    /// Returns the error of an OAuth2 error response as returned by simple_oauth.
+    pub fn oauth_error(&self) -> Option<OauthError> {
+        serde_json::from_slice(&self.body).ok()
+    }
+
+    /// Returns the most specific error detail found in the response body.
+    fn detail(&self) -> Option<String> {
+        if let Some(error) = self.jsonapi_errors().into_iter().next() {
+            if let Some(detail) = error.detail.or(error.title) {
+                return Some(detail);
+            }
+        }
+
+        if let Some(error) = self.oauth_error() {
+            return Some(match (error.error_description, error.hint) {
+                (Some(description), Some(hint)) => format!("{} ({})", description, hint),
+                (Some(description), None) => description,
+                (None, _) => error.error,
+            });
+        }
+
+        self.drupal_message()
+    }
+}
+
+impl fmt::Display for FailedRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "http request failed with status code {}", self.status)?;
+
+        match self.detail() {
+            Some(detail) => write!(f, ": {}", detail),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for FailedRequestError {}
+
+/// A single error object of a JSON:API error response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonApiError {
+    /// The HTTP status code applicable to this error.
+    #[serde(default, deserialize_with = "deserialize_status")]
+    pub status: Option<String>,
+    pub title: Option<String>,
+    pub detail: Option<String>,
+    pub source: Option<JsonApiErrorSource>,
+}
+
+/// References the source of a JSON:API error.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonApiErrorSource {
+    /// JSON pointer to the value in the request document
+    /// that caused the error, e.g. `/data/attributes/title`.
+    pub pointer: Option<String>,
+}
+
+/// An OAuth2 error response, e.g. from the `/oauth/token` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OauthError {
+    /// The error code, e.g. `invalid_client`.
+    pub error: String,
+    pub error_description: Option<String>,
+    pub hint: Option<String>,
+}
+
+/// JSON:API requires the status to be a string, but
+/// some implementations send a number instead.
+fn deserialize_status<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Status {
+        String(String),
+        Number(u16),
+    }
+
+    Ok(
+        Option::<Status>::deserialize(deserializer)?.map(|status| match status {
+            Status::String(status) => status,
+            Status::Number(status) => status.to_string(),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed_request(status: StatusCode, body: &'static str) -> FailedRequestError {
+        FailedRequestError {
+            status,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_jsonapi_errors() {
+        let err = failed_request(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            r#"{"jsonapi": {"version": "1.0"}, "errors": [{"title": "Unprocessable Entity", "status": "422", "detail": "title: This value should not be null.", "source": {"pointer": "/data/attributes/title"}}]}"#,
+        );
+
+        let errors = err.jsonapi_errors();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].status.as_deref(), Some("422"));
+        assert_eq!(errors[0].title.as_deref(), Some("Unprocessable Entity"));
+        assert_eq!(
+            errors[0].source.as_ref().and_then(|s| s.pointer.as_deref()),
+            Some("/data/attributes/title")
+        );
+        assert_eq!(
+            err.to_string(),
+            "http request failed with status code 422 Unprocessable Entity: title: This value should not be null."
+        );
+    }
+
+    #[test]
+    fn test_drupal_message() {
+        let err = failed_request(StatusCode::NOT_FOUND, r#"{"message": "No route found."}"#);
+
+        assert!(err.jsonapi_errors().is_empty());
+        assert!(err.oauth_error().is_none());
+        assert_eq!(err.drupal_message().as_deref(), Some("No route found."));
+        assert_eq!(
+            err.to_string(),
+            "http request failed with status code 404 Not Found: No route found."
+        );
+    }
+
+    #[test]
+    fn test_oauth_error() {
+        let err = failed_request(
+            StatusCode::UNAUTHORIZED,
+            r#"{"error": "invalid_client", "error_description": "Client authentication failed", "message": "Client authentication failed", "hint": "Check the configuration"}"#,
+        );
+
+        let oauth_error = err.oauth_error().expect("must be an oauth error");
+
+        assert_eq!(oauth_error.error, "invalid_client");
+        assert_eq!(oauth_error.hint.as_deref(), Some("Check the configuration"));
+        assert_eq!(
+            err.to_string(),
+            "http request failed with status code 401 Unauthorized: Client authentication failed (Check the configuration)"
+        );
+    }
+
+    #[test]
+    fn test_unknown_body() {
+        let err = failed_request(StatusCode::BAD_GATEWAY, "<html>Bad Gateway</html>");
+
+        assert!(err.jsonapi_errors().is_empty());
+        assert!(err.drupal_message().is_none());
+        assert_eq!(
+            err.to_string(),
+            "http request failed with status code 502 Bad Gateway"
+        );
+    }
+}
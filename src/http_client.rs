@@ -1,12 +1,23 @@
-use core::fmt::{self, Debug};
-use std::{
-    error::{self, Error},
-    future::Future,
-};
+use core::fmt::Debug;
+use std::{borrow::Cow, future::Future, pin::Pin, sync::Arc, time::Duration};
 
-use http::{HeaderMap, HeaderName, HeaderValue};
-use reqwest::{Body, Client, Method, RequestBuilder, Response};
+use bytes::Bytes;
+use futures_core::Stream;
+use http::{header, request::Parts, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
 use serde::de::DeserializeOwned;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    language::LangcodeMode,
+    middleware::{Middleware, Next, RequestContext},
+    transport::{self, Body, Request, Response, ResponseExt, Transport},
+    url_builder, MaintenanceGate, RateLimiter, RetryPolicy,
+};
+
+pub use crate::error::{
+    DrupalkitError, FailedRequestError, JsonApiError, JsonApiErrorSource, OauthError,
+};
+pub use crate::response::{CacheMaxAge, DrupalResponse};
 
 /// Additional option for request.
 #[derive(Clone)]
@@ -22,33 +33,33 @@ pub enum HttpRequestOption {
     /// Disables the use of a authentication strategy therefore
     /// making it an anonymous request.
     Anonymous,
+    /// Retries the request according to the given policy.
+    /// Takes precedence over the value from `get_retry_policy`.
+    Retry(RetryPolicy),
+    /// Disables retrying the request.
+    NoRetry,
+    /// Adds query parameters to the request URL.
+    /// The parameters are percent-encoded and appended
+    /// to any query already present in the path.
+    Query(Vec<(String, String)>),
+    /// Fails the request with `DrupalkitError::Timeout` if it did not
+    /// complete within the given duration, including all retries.
+    /// Takes precedence over the value from `get_timeout`.
+    Timeout(Duration),
+    /// Aborts the request with `DrupalkitError::Cancelled`
+    /// once the given token is cancelled.
+    Cancel(CancellationToken),
+    /// Bypasses the response cache for this request.
+    /// Has no effect if no cache store is configured.
+    NoCache,
+    /// Requests the content in the given language, sent as configured
+    /// by `get_langcode_mode`. An empty langcode sends no language.
+    /// Takes precedence over the value from `get_langcode`.
+    Langcode(String),
 }
 
-/// Defines an error coming from the HttpClient.
-pub type ClientError = Box<dyn Error + Send + Sync>;
-
-#[derive(Debug)]
-pub struct FailedRequestError {
-    response: Response,
-}
-
-impl From<Response> for FailedRequestError {
-    fn from(value: Response) -> Self {
-        Self { response: value }
-    }
-}
-
-impl fmt::Display for FailedRequestError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "http request failed with status code {}",
-            self.response.status()
-        )
-    }
-}
-
-impl error::Error for FailedRequestError {}
+/// A stream of response body chunks.
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, DrupalkitError>> + Send>>;
 
 /// Provides basic HTTP Client capabilities.
 ///
@@ -61,185 +72,598 @@ pub trait HttpClient {
     /// Make an HTTP request.
     ///
     /// The URL is constructed using the base url from `HttpRequestOption::BaseUrl`or `self.get_base_url()`.
-    /// The given path is appended to the base url to produce the full request URL.
-    ///
-    ///
+    /// The given path is resolved relative to the base url to produce the full request URL,
+    /// so a base url with a subdirectory like `https://example.com/drupal` is supported.
+    /// Query parameters can be added with `HttpRequestOption::Query`.
     fn request(
         &self,
         method: Method,
         path: &str,
         body: impl Into<Body> + Send,
         options: Vec<HttpRequestOption>,
-    ) -> impl Future<Output = Result<Response, ClientError>> + Send
+    ) -> impl Future<Output = Result<Response, DrupalkitError>> + Send
+    where
+        Self: Sync,
+    {
+        execute(self, method, path, body.into(), options)
+    }
+
+    /// The same as `request` but deserializes json response body
+    /// into a struct.
+    fn request_json<T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: impl Into<Body> + Send,
+        options: Vec<HttpRequestOption>,
+    ) -> impl Future<Output = Result<T, DrupalkitError>> + Send
     where
         Self: Sync,
+        T: DeserializeOwned + Debug,
     {
         async move {
-            let mut base_url: String = self.get_base_url().to_string();
-            let mut header_map = HeaderMap::new();
-            let mut no_before_after = false;
-
-            // Handle additional request options.
-            for option in &options {
-                match option {
-                    HttpRequestOption::Header(key, value) => {
-                        header_map.insert(key.clone(), value.clone());
-                    }
-                    HttpRequestOption::BaseUrl(url) => {
-                        base_url = url.clone();
-                    }
-                    HttpRequestOption::NoBeforeAfter => {
-                        no_before_after = true;
-                    }
-                    _ => {}
-                }
-            }
+            let options = with_json_content_type(options);
 
-            let url = format!("{}{}", base_url, path);
-            let client = self.get_http_client();
+            execute_json(self, method, path, body.into(), options)
+                .await
+                .map(DrupalResponse::into_data)
+        }
+    }
 
-            // Create a request builder and add modified headers.
-            let req_builder = client.request(method, url).headers(header_map).body(body);
+    /// The same as `request_json` but also returns the response metadata,
+    /// like the status, headers and the cache tags, contexts and max-age.
+    ///
+    /// Fails with `DrupalkitError::Status` if the response
+    /// has a non-success status code.
+    fn request_json_with_meta<T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: impl Into<Body> + Send,
+        options: Vec<HttpRequestOption>,
+    ) -> impl Future<Output = Result<DrupalResponse<T>, DrupalkitError>> + Send
+    where
+        Self: Sync,
+        T: DeserializeOwned,
+    {
+        async move {
+            let options = with_json_content_type(options);
 
-            // Allow alteration of request in impl.
-            // Only on requests without the `HttpRequestOptio::NoBeforeAfter` option.
-            // This is to prevent infinite loops if a `before_request` handler
-            // if the handler itself calls another `request`.
-            let req_builder = if !no_before_after {
-                match self.before_request(req_builder, path, options).await {
-                    Ok(req_builder) => req_builder,
-                    Err(err) => return Err(err),
-                }
-            } else {
-                req_builder
-            };
-
-            // Build the request.
-            let req = req_builder.build()?;
-
-            // Execute the request.
-            match client.execute(req).await {
-                Ok(response) => {
-                    // Allow alteration of response in impl.
-                    // Only on requests without the `HttpRequestOptio::NoBeforeAfter` option.
-                    let response = if !no_before_after {
-                        match self.after_request(response).await {
-                            Ok(response) => response,
-                            Err(err) => return Err(err),
-                        }
-                    } else {
-                        response
-                    };
-
-                    Ok(response)
-                }
-                Err(err) => Err(Box::new(err)),
-            }
+            execute_json(self, method, path, body.into(), options).await
         }
     }
 
-    /// The same as `request` but deserializes json response body
-    /// into a struct.
-    fn request_json<T>(
+    /// The same as `request` but returns the response body
+    /// as a stream of chunks instead of buffering it.
+    ///
+    /// Fails with `DrupalkitError::Status` if the response
+    /// has a non-success status code.
+    fn request_stream(
         &self,
         method: Method,
         path: &str,
         body: impl Into<Body> + Send,
         options: Vec<HttpRequestOption>,
-    ) -> impl Future<Output = Result<T, ClientError>> + Send
+    ) -> impl Future<Output = Result<ByteStream, DrupalkitError>> + Send
     where
         Self: Sync,
-        T: DeserializeOwned + Debug,
     {
         async move {
-            // Add Content-Type: application/json header by default
-            // Only add Content-Type if not already set
-            let mut options = options;
-            if !options.iter().any(|opt| match opt {
-                HttpRequestOption::Header(name, _) => name == "content-type",
-                _ => false,
-            }) {
-                options.push(HttpRequestOption::Header(
-                    HeaderName::from_static("content-type"),
-                    HeaderValue::from_static("application/json"),
-                ));
-            }
-
-            match self.request(method, path, body, options).await {
-                Ok(response) => {
-                    if response.status().is_success() {
-                        let bytes = response.bytes().await?;
-                        let payload = serde_json::from_slice(&bytes)?;
-
-                        Ok(payload)
-                    } else {
-                        Err(Box::new(FailedRequestError::from(response))
-                            as Box<dyn Error + Send + Sync>)
-                    }
-                }
-                Err(err) => Err(err),
+            let response = self.request(method, path, body, options).await?;
+
+            if !response.status().is_success() {
+                return Err(FailedRequestError::from_response(response).await?.into());
             }
+
+            Ok(response.bytes_stream())
         }
     }
 
     /// Modify the request before being sent.
     ///
-    /// Using the given `reqwest::RequestBuilder` you can add
+    /// Using the given `http::request::Parts` you can add
     /// headers and do other stuff to the request.
     fn before_request(
         &self,
-        req_builder: RequestBuilder,
+        _parts: &mut Parts,
         _path: &str,
         _options: Vec<HttpRequestOption>,
-    ) -> impl Future<Output = Result<RequestBuilder, ClientError>> + Send {
-        async { Ok(req_builder) }
+    ) -> impl Future<Output = Result<(), DrupalkitError>> + Send {
+        async { Ok(()) }
     }
 
     /// Modify a successful response.
     ///
-    /// This method alters the `reqwest::Response` returned
+    /// This method alters the `Response` returned
     /// from the `request` method.
     fn after_request(
         &self,
         response: Response,
-    ) -> impl Future<Output = Result<Response, ClientError>> + Send {
+    ) -> impl Future<Output = Result<Response, DrupalkitError>> + Send {
         async { Ok(response) }
     }
 
-    /// Return the underlying instance of `reqwest::Client`.
+    /// Handle an unauthorized response.
+    ///
+    /// Invalidate the credentials used for the request and return `true`
+    /// to replay the request once with fresh credentials.
+    /// A replayed request is never replayed again.
+    fn on_unauthorized(
+        &self,
+        _path: &str,
+        _options: Vec<HttpRequestOption>,
+    ) -> impl Future<Output = bool> + Send {
+        async { false }
+    }
+
+    /// Handle a `403 Forbidden` response rejecting the `X-CSRF-Token`
+    /// header of the request.
+    ///
+    /// Invalidate the CSRF token used for the request and return `true`
+    /// to replay the request once with a fresh token.
+    /// A replayed request is never replayed again.
+    fn on_csrf_token_invalid(
+        &self,
+        _path: &str,
+        _options: Vec<HttpRequestOption>,
+    ) -> impl Future<Output = bool> + Send {
+        async { false }
+    }
+
+    /// Return the transport sending the requests.
     ///
     /// # Example
     ///
     /// ```rust
+    /// # #[cfg(feature = "reqwest")] {
     /// use drupal_kit::http_client::HttpClient;
+    /// use drupal_kit::transport::{ReqwestTransport, Transport};
     ///
     /// struct MyHttpClient {
-    ///     http_client: reqwest::Client,
+    ///     transport: ReqwestTransport,
     /// }
     ///
     /// impl MyHttpClient {
     ///     pub fn new() -> Self {
-    ///         let http_client = reqwest::Client::new();
+    ///         let transport = ReqwestTransport::default();
     ///
     ///         Self {
-    ///             http_client,
+    ///             transport,
     ///         }
     ///     }
     /// }
     ///
     /// impl HttpClient for MyHttpClient {
-    ///     fn get_http_client(&self) -> &reqwest::Client {
-    ///         &self.http_client
+    ///     fn get_transport(&self) -> &dyn Transport {
+    ///         &self.transport
     ///     }
     ///
     ///     fn get_base_url(&self) -> &str {
     ///         todo!()
     ///     }
     /// }
+    /// # }
     /// ```
-    fn get_http_client(&self) -> &Client;
+    fn get_transport(&self) -> &dyn Transport;
+
+    /// Returns the retry policy used for every request,
+    /// unless explicitly set with `HttpRequestOption::Retry`
+    /// or disabled with `HttpRequestOption::NoRetry`.
+    fn get_retry_policy(&self) -> Option<&RetryPolicy> {
+        None
+    }
+
+    /// Returns the headers added to every request, unless
+    /// set explicitly with `HttpRequestOption::Header`.
+    fn get_default_headers(&self) -> Option<&HeaderMap> {
+        None
+    }
+
+    /// Returns the langcode used for every request,
+    /// unless explicitly set with `HttpRequestOption::Langcode`.
+    fn get_langcode(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns how the langcode of a request is sent.
+    fn get_langcode_mode(&self) -> LangcodeMode {
+        LangcodeMode::PathPrefix
+    }
+
+    /// Returns the timeout used for every request,
+    /// unless explicitly set with `HttpRequestOption::Timeout`.
+    fn get_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the rate limiter every request waits for before being sent.
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        None
+    }
+
+    /// Returns the gate pausing every request while
+    /// the site is in maintenance mode.
+    fn get_maintenance_gate(&self) -> Option<&MaintenanceGate> {
+        None
+    }
+
+    /// Returns the middlewares every request is passed through.
+    fn get_middlewares(&self) -> &[Arc<dyn Middleware>] {
+        &[]
+    }
+
+    /// Checks whether `before_request` authenticates requests using
+    /// an auth strategy. Responses to such requests are never cached.
+    fn has_auth_strategy(&self) -> bool {
+        false
+    }
 
     /// Returns the baseurl used for every request,
     /// unless explicitly set with `HttpRequestOption::BaseUrl`.
     fn get_base_url(&self) -> &str;
 }
+
+/// Executes the request flow of `HttpClient::request`.
+pub(crate) async fn execute<C>(
+    http_client: &C,
+    method: Method,
+    path: &str,
+    body: Body,
+    options: Vec<HttpRequestOption>,
+) -> Result<Response, DrupalkitError>
+where
+    C: HttpClient + Sync + ?Sized,
+{
+    let mut base_url: String = http_client.get_base_url().to_string();
+    let mut header_map = http_client
+        .get_default_headers()
+        .cloned()
+        .unwrap_or_default();
+    let mut no_before_after = false;
+    let mut retry_policy = http_client.get_retry_policy().cloned();
+    let mut query = Vec::new();
+    let mut timeout = http_client.get_timeout();
+    let mut cancellation_token = None;
+    let mut langcode = http_client.get_langcode().map(str::to_owned);
+
+    // Handle additional request options.
+    for option in &options {
+        match option {
+            HttpRequestOption::Header(key, value) => {
+                header_map.insert(key.clone(), value.clone());
+            }
+            HttpRequestOption::BaseUrl(url) => {
+                base_url = url.clone();
+            }
+            HttpRequestOption::NoBeforeAfter => {
+                no_before_after = true;
+            }
+            HttpRequestOption::Retry(policy) => {
+                retry_policy = Some(policy.clone());
+            }
+            HttpRequestOption::NoRetry => {
+                retry_policy = None;
+            }
+            HttpRequestOption::Query(pairs) => {
+                query.extend_from_slice(pairs);
+            }
+            HttpRequestOption::Timeout(duration) => {
+                timeout = Some(*duration);
+            }
+            HttpRequestOption::Cancel(token) => {
+                cancellation_token = Some(token.clone());
+            }
+            HttpRequestOption::Langcode(value) => {
+                langcode = Some(value.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let langcode = langcode.filter(|langcode| !langcode.is_empty());
+    let mut url_path = Cow::Borrowed(path);
+
+    match (langcode, http_client.get_langcode_mode()) {
+        (Some(langcode), LangcodeMode::PathPrefix) => {
+            url_path = url_builder::prefix_langcode(path, &langcode);
+        }
+        (Some(langcode), LangcodeMode::AcceptLanguage) => {
+            if !header_map.contains_key(header::ACCEPT_LANGUAGE) {
+                header_map.insert(header::ACCEPT_LANGUAGE, HeaderValue::from_str(&langcode)?);
+            }
+        }
+        (None, _) => {}
+    }
+
+    #[cfg(feature = "tracing")]
+    let span = crate::trace::request_span(&method, path);
+    #[cfg(feature = "tracing")]
+    span.record("trace_id", crate::trace::propagate(&mut header_map));
+
+    let future = async {
+        let url = url_builder::build_url(&base_url, &url_path, &query)?;
+
+        // Create the request and add modified headers.
+        let mut req = http::Request::builder()
+            .method(method)
+            .uri(url.as_str())
+            .body(body)?;
+        *req.headers_mut() = header_map;
+
+        // Keep a copy of the request in case it must be replayed
+        // after an unauthorized response. Requests with a streaming
+        // body can not be copied and are never replayed.
+        let mut replay_req = if !no_before_after {
+            transport::try_clone_request(&req)
+        } else {
+            None
+        };
+
+        let mut response = send_request(
+            http_client,
+            req,
+            path,
+            &options,
+            no_before_after,
+            retry_policy.clone(),
+        )
+        .await?;
+
+        // Replay the request once the site is back online,
+        // if it was answered in maintenance mode.
+        if response.status() == StatusCode::SERVICE_UNAVAILABLE {
+            if let Some(gate) = http_client.get_maintenance_gate() {
+                // Buffer the body to look for the maintenance page,
+                // it is still returned if the request is not replayed.
+                let (parts, body) = response.into_parts();
+                let body = body.bytes().await?;
+                let error =
+                    FailedRequestError::new(parts.status, parts.headers.clone(), body.clone());
+
+                response = Response::from_parts(parts, body.into());
+
+                if error.is_maintenance() {
+                    gate.set_offline(error);
+
+                    if let Some(req) = replay_req.as_ref().and_then(transport::try_clone_request) {
+                        response = send_request(
+                            http_client,
+                            req,
+                            path,
+                            &options,
+                            no_before_after,
+                            retry_policy.clone(),
+                        )
+                        .await?;
+                    }
+                }
+            }
+        }
+
+        // Replay the request once, if the implementation
+        // invalidated the credentials used for this request.
+        if response.status() == StatusCode::UNAUTHORIZED {
+            if let Some(req) = replay_req.take() {
+                if http_client.on_unauthorized(path, options.clone()).await {
+                    response = send_request(
+                        http_client,
+                        req,
+                        path,
+                        &options,
+                        no_before_after,
+                        retry_policy.clone(),
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        // Replay the request once, if Drupal rejected the CSRF
+        // token and the implementation invalidated it.
+        if response.status() == StatusCode::FORBIDDEN {
+            if let Some(req) = replay_req {
+                // Buffer the body to look for the error message,
+                // it is still returned if the request is not replayed.
+                let (parts, body) = response.into_parts();
+                let body = body.bytes().await?;
+                let is_csrf_token_invalid = is_csrf_token_invalid(&body);
+
+                response = Response::from_parts(parts, body.into());
+
+                if is_csrf_token_invalid
+                    && http_client
+                        .on_csrf_token_invalid(path, options.clone())
+                        .await
+                {
+                    response = send_request(
+                        http_client,
+                        req,
+                        path,
+                        &options,
+                        no_before_after,
+                        retry_policy,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        // Allow alteration of response in impl.
+        // Only on requests without the `HttpRequestOptio::NoBeforeAfter` option.
+        if !no_before_after {
+            http_client.after_request(response).await
+        } else {
+            Ok(response)
+        }
+    };
+
+    let future = with_deadline(future, timeout, cancellation_token);
+
+    #[cfg(feature = "tracing")]
+    let future = crate::trace::instrument(span, future);
+
+    // Box the request flow, as its future is large and
+    // `request` is reentered by auth strategies.
+    Box::pin(future).await
+}
+
+/// Adds the `Content-Type: application/json` header,
+/// unless a `Content-Type` is already set.
+fn with_json_content_type(mut options: Vec<HttpRequestOption>) -> Vec<HttpRequestOption> {
+    if !options.iter().any(|opt| match opt {
+        HttpRequestOption::Header(name, _) => name == "content-type",
+        _ => false,
+    }) {
+        options.push(HttpRequestOption::Header(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        ));
+    }
+
+    options
+}
+
+/// Executes the request flow of `HttpClient::request_json_with_meta`,
+/// without adding a `Content-Type` header.
+pub(crate) async fn execute_json<C, T>(
+    http_client: &C,
+    method: Method,
+    path: &str,
+    body: Body,
+    options: Vec<HttpRequestOption>,
+) -> Result<DrupalResponse<T>, DrupalkitError>
+where
+    C: HttpClient + Sync + ?Sized,
+    T: DeserializeOwned,
+{
+    // Reading the body is bound by the timeout
+    // and cancellation token as well.
+    let mut timeout = http_client.get_timeout();
+    let mut cancellation_token = None;
+
+    for option in &options {
+        match option {
+            HttpRequestOption::Timeout(duration) => {
+                timeout = Some(*duration);
+            }
+            HttpRequestOption::Cancel(token) => {
+                cancellation_token = Some(token.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let future = async move {
+        match http_client.request(method, path, body, options).await {
+            Ok(response) => {
+                if response.status().is_success() {
+                    let metadata = DrupalResponse::metadata(&response);
+                    let bytes = response.bytes().await?;
+                    let data = DrupalkitError::decode(bytes)?;
+
+                    Ok(metadata.map(|()| data))
+                } else {
+                    Err(FailedRequestError::from_response(response).await?.into())
+                }
+            }
+            Err(err) => Err(err),
+        }
+    };
+
+    with_deadline(future, timeout, cancellation_token).await
+}
+
+/// Checks whether the body of a `403 Forbidden` response
+/// is Drupal's error for an invalid `X-CSRF-Token` header.
+fn is_csrf_token_invalid(body: &[u8]) -> bool {
+    const MESSAGE: &[u8] = b"X-CSRF-Token request header is invalid";
+
+    body.windows(MESSAGE.len()).any(|window| window == MESSAGE)
+}
+
+/// Runs the future until it completes, the timeout
+/// elapses or the cancellation token is cancelled.
+async fn with_deadline<F, T>(
+    future: F,
+    timeout: Option<Duration>,
+    cancellation_token: Option<CancellationToken>,
+) -> Result<T, DrupalkitError>
+where
+    F: Future<Output = Result<T, DrupalkitError>>,
+{
+    let future = async {
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, future)
+                .await
+                .unwrap_or(Err(DrupalkitError::Timeout)),
+            None => future.await,
+        }
+    };
+
+    match cancellation_token {
+        Some(token) => token
+            .run_until_cancelled(future)
+            .await
+            .unwrap_or(Err(DrupalkitError::Cancelled)),
+        None => future.await,
+    }
+}
+
+/// Sends a single request, invoking `before_request`
+/// and the middleware chain.
+async fn send_request<C>(
+    http_client: &C,
+    req: Request,
+    path: &str,
+    options: &[HttpRequestOption],
+    no_before_after: bool,
+    retry_policy: Option<RetryPolicy>,
+) -> Result<Response, DrupalkitError>
+where
+    C: HttpClient + Sync + ?Sized,
+{
+    // Wait until the site is back online.
+    if let Some(gate) = http_client.get_maintenance_gate() {
+        gate.wait_until_online(http_client.get_transport()).await?;
+    }
+
+    // Allow alteration of request in impl.
+    // Only on requests without the `HttpRequestOptio::NoBeforeAfter` option.
+    // This is to prevent infinite loops if a `before_request` handler
+    // if the handler itself calls another `request`.
+    let req = if !no_before_after {
+        let (mut parts, body) = req.into_parts();
+
+        http_client
+            .before_request(&mut parts, path, options.to_vec())
+            .await?;
+
+        Request::from_parts(parts, body)
+    } else {
+        req
+    };
+
+    // Execute the request through the middleware chain,
+    // retrying it if a retry policy applies. Each attempt
+    // waits until the rate limit allows sending it.
+    let anonymous = no_before_after
+        || !http_client.has_auth_strategy()
+        || options
+            .iter()
+            .any(|option| matches!(option, HttpRequestOption::Anonymous));
+
+    let ctx = RequestContext {
+        path,
+        options,
+        anonymous,
+    };
+
+    Next::new(
+        http_client.get_transport(),
+        http_client.get_middlewares(),
+        retry_policy,
+        http_client.get_rate_limiter(),
+    )
+    .run(req, &ctx)
+    .await
+}
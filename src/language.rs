@@ -0,0 +1,108 @@
+//! Requests for multilingual Drupal sites.
+
+use http::Method;
+use serde::Deserialize;
+
+use crate::{
+    http_client::{HttpClient, HttpRequestOption},
+    Drupalkit, DrupalkitError,
+};
+
+/// How the langcode of a request is sent to Drupal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LangcodeMode {
+    /// Prefix the path with the langcode, e.g. `/de/jsonapi/node/article`,
+    /// for sites negotiating the language by URL.
+    #[default]
+    PathPrefix,
+    /// Send the langcode in the `Accept-Language` header,
+    /// for sites negotiating the language by browser settings.
+    AcceptLanguage,
+}
+
+/// A language enabled on the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Language {
+    pub langcode: String,
+    pub label: String,
+    /// The text direction, `ltr` or `rtl`.
+    pub direction: String,
+    pub weight: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguagesResponse {
+    data: Vec<LanguageResource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageResource {
+    attributes: LanguageAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageAttributes {
+    #[serde(rename = "drupal_internal__id")]
+    langcode: String,
+    label: String,
+    direction: String,
+    weight: i32,
+    #[serde(default)]
+    locked: bool,
+}
+
+impl Drupalkit {
+    /// Lists the languages enabled on the site, ordered by weight.
+    ///
+    /// Uses the `configurable_language` resources of the JSON:API module.
+    /// The locked languages `und` and `zxx` are not listed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use drupal_kit::Drupalkit;
+    ///
+    /// # async fn run(drupalkit: Drupalkit) -> Result<(), drupal_kit::DrupalkitError> {
+    /// for language in drupalkit.languages().await? {
+    ///     println!("{}: {}", language.langcode, language.label);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn languages(&self) -> Result<Vec<Language>, DrupalkitError> {
+        let response: LanguagesResponse = self
+            .request_json(
+                Method::GET,
+                "/jsonapi/configurable_language/configurable_language",
+                "",
+                vec![
+                    // The list does not depend on the language of the request.
+                    HttpRequestOption::Langcode(String::new()),
+                ],
+            )
+            .await?;
+
+        // JSON:API does not support sorting config entities.
+        let mut languages: Vec<Language> = response
+            .data
+            .into_iter()
+            .map(|resource| resource.attributes)
+            .filter(|attributes| !attributes.locked)
+            .map(|attributes| Language {
+                langcode: attributes.langcode,
+                label: attributes.label,
+                direction: attributes.direction,
+                weight: attributes.weight,
+            })
+            .collect();
+
+        languages.sort_by_key(|language| language.weight);
+
+        Ok(languages)
+    }
+}
@@ -1,10 +1,38 @@
 pub mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
 mod client;
+#[cfg(feature = "config")]
+pub mod config;
+mod csrf;
+pub mod download;
 mod drupalkit_builder;
+mod error;
 pub mod http_client;
+pub mod language;
+mod maintenance;
+pub mod middleware;
+mod rate_limit;
+mod request_builder;
+mod response;
+mod retry;
+mod secret;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod transport;
+mod url_builder;
 
 pub use client::Drupalkit;
 pub use drupalkit_builder::DrupalkitBuilder as Builder;
+pub use error::{BuildError, DrupalkitError};
+pub use maintenance::{MaintenanceGate, MaintenancePolicy};
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use request_builder::RequestBuilder;
+pub use retry::RetryPolicy;
+pub use secret::Secret;
 
 pub mod http {
     pub use http::Method;
@@ -12,5 +40,7 @@ pub mod http {
     pub use http::HeaderValue;
 }
 
+#[cfg(feature = "tower")]
+pub mod service;
 #[cfg(feature = "simple-oauth")]
 pub mod simple_oauth;
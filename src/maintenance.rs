@@ -0,0 +1,263 @@
+use std::{sync::Mutex, time::Duration};
+
+use http::{HeaderMap, Method, StatusCode, Uri};
+use tokio::time::Instant;
+
+use crate::{
+    error::FailedRequestError,
+    transport::{self, Body, Transport},
+    DrupalkitError,
+};
+
+const DEFAULT_MAX_WAIT: Duration = Duration::from_secs(300);
+const DEFAULT_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Defines how requests wait while the site is in maintenance mode.
+///
+/// Once a request is answered with Drupal's maintenance mode response,
+/// all requests of the `Drupalkit` instance and its clones are paused.
+/// While paused, the probe path is requested anonymously in the
+/// configured interval, until the site is back online or the maximum
+/// wait elapsed. A request still paused then fails with
+/// `DrupalkitError::Maintenance` and the pause ends, so later
+/// requests are sent to the site again.
+///
+/// The request that ran into the maintenance mode is replayed
+/// once the site is back online, unless its body is streamed.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "reqwest")] {
+/// use std::time::Duration;
+/// use drupal_kit::{Builder, MaintenancePolicy};
+///
+/// let drupalkit = Builder::new()
+///     .set_base_url("https://example.com")
+///     .set_maintenance_policy(
+///         MaintenancePolicy::new()
+///             .set_max_wait(Duration::from_secs(600))
+///             .set_probe_path("/jsonapi"),
+///     )
+///     .build();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaintenancePolicy {
+    max_wait: Duration,
+    probe_path: String,
+    probe_interval: Duration,
+}
+
+impl Default for MaintenancePolicy {
+    fn default() -> Self {
+        Self {
+            max_wait: DEFAULT_MAX_WAIT,
+            probe_path: "/".to_owned(),
+            probe_interval: DEFAULT_PROBE_INTERVAL,
+        }
+    }
+}
+
+impl MaintenancePolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long requests wait for the site to be back online,
+    /// counted from the first maintenance mode response.
+    ///
+    /// Defaults to 5 minutes.
+    pub fn set_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+
+        self
+    }
+
+    /// Sets the path requested to check whether the site is back online.
+    ///
+    /// Defaults to the front page `/`.
+    pub fn set_probe_path(mut self, probe_path: &str) -> Self {
+        self.probe_path = probe_path.to_owned();
+
+        self
+    }
+
+    /// Sets the delay between two requests to the probe path.
+    ///
+    /// Defaults to 5 seconds.
+    pub fn set_probe_interval(mut self, probe_interval: Duration) -> Self {
+        self.probe_interval = probe_interval;
+
+        self
+    }
+
+    pub fn max_wait(&self) -> Duration {
+        self.max_wait
+    }
+
+    pub fn probe_path(&self) -> &str {
+        &self.probe_path
+    }
+
+    pub fn probe_interval(&self) -> Duration {
+        self.probe_interval
+    }
+}
+
+enum Probe {
+    Online,
+    /// Carries the response, if it is a maintenance mode response.
+    Offline(Option<FailedRequestError>),
+}
+
+struct Offline {
+    since: Instant,
+    probed_at: Instant,
+    /// The last maintenance mode response, returned once the wait elapsed.
+    error: FailedRequestError,
+}
+
+/// Pauses the requests of a `Drupalkit` instance and all its clones
+/// while the site is in maintenance mode.
+///
+/// Created by `DrupalkitBuilder` from the configured `MaintenancePolicy`.
+pub struct MaintenanceGate {
+    policy: MaintenancePolicy,
+    probe_uri: Uri,
+    probe_headers: HeaderMap,
+    offline: Mutex<Option<Offline>>,
+    /// Held while waiting for and sending a probe,
+    /// so only one request probes at a time.
+    probe: tokio::sync::Mutex<()>,
+}
+
+impl MaintenanceGate {
+    pub(crate) fn new(policy: MaintenancePolicy, probe_uri: Uri, probe_headers: HeaderMap) -> Self {
+        Self {
+            policy,
+            probe_uri,
+            probe_headers,
+            offline: Mutex::new(None),
+            probe: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Checks whether requests are paused, because the
+    /// site was last seen in maintenance mode.
+    pub fn is_offline(&self) -> bool {
+        self.lock().is_some()
+    }
+
+    /// Pauses requests until the site is back online.
+    pub(crate) fn set_offline(&self, error: FailedRequestError) {
+        let mut offline = self.lock();
+
+        match offline.as_mut() {
+            Some(offline) => offline.error = error,
+            None => {
+                let now = Instant::now();
+
+                *offline = Some(Offline {
+                    since: now,
+                    probed_at: now,
+                    error,
+                });
+            }
+        }
+    }
+
+    /// Waits until the site is back online.
+    ///
+    /// Fails with `DrupalkitError::Maintenance` if the site is
+    /// still in maintenance mode once the maximum wait elapsed.
+    pub(crate) async fn wait_until_online(
+        &self,
+        transport: &dyn Transport,
+    ) -> Result<(), DrupalkitError> {
+        if !self.is_offline() {
+            return Ok(());
+        }
+
+        loop {
+            let _probe = self.probe.lock().await;
+
+            // Another request may have probed in the meantime.
+            let next_probe = {
+                let mut offline = self.lock();
+
+                match offline.as_ref() {
+                    None => return Ok(()),
+                    Some(state) => {
+                        let next_probe = state.probed_at + self.policy.probe_interval;
+
+                        // Give up, so the next request is sent to the site again.
+                        if next_probe > state.since + self.policy.max_wait {
+                            let error = state.error.clone();
+                            *offline = None;
+
+                            return Err(DrupalkitError::Maintenance(error));
+                        }
+
+                        next_probe
+                    }
+                }
+            };
+
+            tokio::time::sleep_until(next_probe).await;
+
+            let probe = self.probe(transport).await;
+            let mut offline = self.lock();
+
+            match (probe, offline.as_mut()) {
+                (Probe::Online, _) => {
+                    *offline = None;
+
+                    return Ok(());
+                }
+                (Probe::Offline(error), Some(offline)) => {
+                    offline.probed_at = Instant::now();
+
+                    if let Some(error) = error {
+                        offline.error = error;
+                    }
+                }
+                (Probe::Offline(_), None) => return Ok(()),
+            }
+        }
+    }
+
+    /// Requests the probe path.
+    ///
+    /// Connection errors and other `503 Service Unavailable` responses,
+    /// e.g. of a proxy while Drupal restarts, count as still offline.
+    async fn probe(&self, transport: &dyn Transport) -> Probe {
+        let mut req = http::Request::new(Body::empty());
+        *req.method_mut() = Method::GET;
+        *req.uri_mut() = self.probe_uri.clone();
+        *req.headers_mut() = self.probe_headers.clone();
+
+        let response = match transport::send(transport, req).await {
+            Ok(response) => response,
+            Err(_) => return Probe::Offline(None),
+        };
+
+        if response.status() != StatusCode::SERVICE_UNAVAILABLE {
+            return Probe::Online;
+        }
+
+        let (parts, body) = response.into_parts();
+        let error = body
+            .bytes()
+            .await
+            .ok()
+            .map(|body| FailedRequestError::new(parts.status, parts.headers, body))
+            .filter(FailedRequestError::is_maintenance);
+
+        Probe::Offline(error)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Offline>> {
+        self.offline.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use crate::{
+    http_client::HttpRequestOption,
+    retry,
+    transport::{Request, Response, Transport},
+    DrupalkitError, RateLimiter, RetryPolicy,
+};
+use async_trait::async_trait;
+
+/// Information about the request passed through the middleware chain.
+pub struct RequestContext<'a> {
+    /// The path as passed to `HttpClient::request`.
+    pub path: &'a str,
+    /// The options as passed to `HttpClient::request`.
+    pub options: &'a [HttpRequestOption],
+    /// Whether no auth strategy applied to the request, as it is
+    /// `HttpRequestOption::Anonymous` or the client has no auth strategy.
+    pub anonymous: bool,
+}
+
+/// Wraps the execution of a request.
+///
+/// Middlewares are registered on the `DrupalkitBuilder` and invoked
+/// in the order of registration. Each middleware receives the request
+/// after `before_request` was applied and may alter it before passing it
+/// on to `next`. The response returned from `next` may be altered as well.
+///
+/// A middleware can short-circuit the chain by returning a response
+/// without calling `next`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "reqwest")] {
+/// use async_trait::async_trait;
+/// use drupal_kit::middleware::{Middleware, Next, RequestContext};
+/// use drupal_kit::{Builder, DrupalkitError};
+/// use drupal_kit::transport::{Request, Response, ResponseExt};
+///
+/// struct LogMiddleware;
+///
+/// #[async_trait]
+/// impl Middleware for LogMiddleware {
+///     async fn handle(
+///         &self,
+///         req: Request,
+///         ctx: &RequestContext<'_>,
+///         next: Next<'_>,
+///     ) -> Result<Response, DrupalkitError> {
+///         println!("{} {}", req.method(), ctx.path);
+///
+///         let response = next.run(req, ctx).await?;
+///
+///         println!("{:?} {}: {}", response.url(), ctx.path, response.status());
+///
+///         Ok(response)
+///     }
+/// }
+///
+/// let drupalkit = Builder::new()
+///     .set_base_url("https://example.com")
+///     .with_middleware(LogMiddleware)
+///     .build();
+/// # }
+/// ```
+#[async_trait]
+pub trait Middleware
+where
+    Self: Send + Sync + 'static,
+{
+    async fn handle(
+        &self,
+        req: Request,
+        ctx: &RequestContext<'_>,
+        next: Next<'_>,
+    ) -> Result<Response, DrupalkitError>;
+}
+
+/// The remaining middleware chain.
+///
+/// The last element of the chain executes the request,
+/// retrying it if a retry policy applies and waiting
+/// for the rate limiter before each attempt.
+pub struct Next<'a> {
+    transport: &'a dyn Transport,
+    middlewares: &'a [Arc<dyn Middleware>],
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<&'a RateLimiter>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        transport: &'a dyn Transport,
+        middlewares: &'a [Arc<dyn Middleware>],
+        retry_policy: Option<RetryPolicy>,
+        rate_limiter: Option<&'a RateLimiter>,
+    ) -> Self {
+        Self {
+            transport,
+            middlewares,
+            retry_policy,
+            rate_limiter,
+        }
+    }
+
+    /// Passes the request on to the next middleware in the chain.
+    pub async fn run(
+        self,
+        req: Request,
+        ctx: &RequestContext<'_>,
+    ) -> Result<Response, DrupalkitError> {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => {
+                let next = Next {
+                    middlewares,
+                    ..self
+                };
+
+                middleware.handle(req, ctx, next).await
+            }
+            None => {
+                retry::execute(
+                    self.transport,
+                    req,
+                    self.retry_policy,
+                    self.rate_limiter,
+                    ctx.path,
+                )
+                .await
+            }
+        }
+    }
+}
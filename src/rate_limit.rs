@@ -0,0 +1,212 @@
+use std::{sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+
+/// Limits the number of requests per second.
+///
+/// Requests are limited using a token bucket, which holds up to
+/// `burst` tokens and is refilled at the configured rate. Each request
+/// takes one token, waiting until a token is available if the bucket is empty.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "reqwest")] {
+/// use drupal_kit::{Builder, RateLimit};
+///
+/// let drupalkit = Builder::new()
+///     .set_base_url("https://example.com")
+///     .set_rate_limit(RateLimit::per_second(20))
+///     .set_path_rate_limit("/oauth/token", RateLimit::per_second(1).set_burst(5))
+///     .build();
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    per_second: f64,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Allows the given number of requests per second.
+    /// The burst defaults to the same number.
+    pub fn per_second(requests: u32) -> Self {
+        let requests = requests.max(1);
+
+        Self {
+            per_second: requests.into(),
+            burst: requests,
+        }
+    }
+
+    /// Allows the given number of requests per minute.
+    /// The burst defaults to one request.
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            per_second: f64::from(requests.max(1)) / 60.0,
+            burst: 1,
+        }
+    }
+
+    /// Sets the number of requests that may be sent at once
+    /// before being limited.
+    pub fn set_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+
+        self
+    }
+}
+
+struct BucketState {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+struct TokenBucket {
+    limit: RateLimit,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(BucketState {
+                tokens: limit.burst.into(),
+                updated_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes a token from the bucket and returns how long to wait
+    /// until the token is actually available.
+    ///
+    /// The bucket may go into debt, so waiting callers are
+    /// served in the order they reserved their token.
+    fn reserve(&self) -> Duration {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.updated_at).as_secs_f64();
+
+        state.tokens =
+            (state.tokens + elapsed * self.limit.per_second).min(self.limit.burst.into());
+        state.updated_at = now;
+        state.tokens -= 1.0;
+
+        if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / self.limit.per_second)
+        }
+    }
+}
+
+/// Limits the request rate of a `Drupalkit` instance and all its clones.
+///
+/// Created by `DrupalkitBuilder` from the configured `RateLimit`s.
+pub struct RateLimiter {
+    global: Option<TokenBucket>,
+    paths: Vec<(String, TokenBucket)>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(global: Option<RateLimit>, paths: Vec<(String, RateLimit)>) -> Self {
+        let mut paths: Vec<(String, TokenBucket)> = paths
+            .into_iter()
+            .map(|(prefix, limit)| (prefix, TokenBucket::new(limit)))
+            .collect();
+
+        // Sort by prefix length, so the most specific prefix matches first.
+        paths.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+
+        Self {
+            global: global.map(TokenBucket::new),
+            paths,
+        }
+    }
+
+    /// Waits until a request to the given path may be sent.
+    ///
+    /// The request must satisfy both the global limit and the limit
+    /// of the longest matching path prefix.
+    pub async fn acquire(&self, path: &str) {
+        let global_delay = self
+            .global
+            .as_ref()
+            .map_or(Duration::ZERO, TokenBucket::reserve);
+
+        let path_delay = self
+            .paths
+            .iter()
+            .find(|(prefix, _)| matches_prefix(path, prefix))
+            .map_or(Duration::ZERO, |(_, bucket)| bucket.reserve());
+
+        let delay = global_delay.max(path_delay);
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Checks whether the path starts with the given prefix,
+/// which must end at a segment boundary: `/user` matches
+/// `/user/1` and `/user?_format=json`, but not `/users`.
+fn matches_prefix(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix).is_some_and(|rest| {
+        rest.is_empty() || prefix.ends_with('/') || rest.starts_with(['/', '?'])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_prefix() {
+        assert!(matches_prefix("/jsonapi", "/jsonapi"));
+        assert!(matches_prefix("/jsonapi/node/article", "/jsonapi"));
+        assert!(matches_prefix("/jsonapi/node/article", "/jsonapi/"));
+        assert!(matches_prefix("/user?_format=json", "/user"));
+        assert!(!matches_prefix("/jsonapix/node", "/jsonapi"));
+        assert!(!matches_prefix("/users", "/user"));
+        assert!(!matches_prefix("/node", "/jsonapi"));
+    }
+
+    #[test]
+    fn test_burst() {
+        let bucket = TokenBucket::new(RateLimit::per_second(1).set_burst(3));
+
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+        assert!(bucket.reserve() > Duration::from_millis(900));
+    }
+
+    #[test]
+    fn test_debt() {
+        let bucket = TokenBucket::new(RateLimit::per_second(10).set_burst(1));
+
+        assert_eq!(bucket.reserve(), Duration::ZERO);
+
+        let first = bucket.reserve();
+        let second = bucket.reserve();
+
+        assert!(first > Duration::from_millis(90) && first <= Duration::from_millis(100));
+        assert!(second > Duration::from_millis(190) && second <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_longest_prefix() {
+        let limiter = RateLimiter::new(
+            None,
+            vec![
+                ("/jsonapi".to_owned(), RateLimit::per_second(100)),
+                ("/jsonapi/node".to_owned(), RateLimit::per_second(1)),
+            ],
+        );
+
+        assert_eq!(limiter.paths[0].0, "/jsonapi/node");
+    }
+}
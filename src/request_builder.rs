@@ -0,0 +1,199 @@
+use http::{header, HeaderValue, Method};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    error::FailedRequestError,
+    http_client::{execute_json, DrupalResponse, HttpClient, HttpRequestOption},
+    transport::{Body, Response},
+    Drupalkit, DrupalkitError,
+};
+
+/// A request built with `Drupalkit::get`, `post`, `patch` or `delete`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use drupal_kit::http_client::HttpRequestOption;
+/// use drupal_kit::Drupalkit;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize)]
+/// struct NewArticle<'a> {
+///     title: &'a str,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Article {
+///     nid: u32,
+/// }
+///
+/// # async fn run(drupalkit: Drupalkit) -> Result<(), drupal_kit::DrupalkitError> {
+/// let article: Article = drupalkit
+///     .post("/node")
+///     .json(&NewArticle { title: "Hello" })
+///     .option(HttpRequestOption::Query(vec![
+///         ("_format".to_owned(), "json".to_owned()),
+///     ]))
+///     .send_json()
+///     .await?;
+///
+/// drupalkit
+///     .delete(&format!("/node/{}", article.nid))
+///     .send_empty()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "the request is only sent by `send`, `send_json` or `send_empty`"]
+pub struct RequestBuilder<'a> {
+    drupalkit: &'a Drupalkit,
+    method: Method,
+    path: String,
+    body: Result<Option<(Body, Option<HeaderValue>)>, DrupalkitError>,
+    options: Vec<HttpRequestOption>,
+}
+
+impl<'a> RequestBuilder<'a> {
+    fn new(drupalkit: &'a Drupalkit, method: Method, path: &str) -> Self {
+        Self {
+            drupalkit,
+            method,
+            path: path.to_owned(),
+            body: Ok(None),
+            options: Vec::new(),
+        }
+    }
+
+    /// Send the given value serialized as JSON,
+    /// with the `Content-Type: application/json` header.
+    ///
+    /// Serialization errors are returned when sending the request.
+    pub fn json<T>(mut self, value: &T) -> Self
+    where
+        T: Serialize + ?Sized,
+    {
+        self.body = serde_json::to_vec(value)
+            .map(|json| {
+                Some((
+                    json.into(),
+                    Some(HeaderValue::from_static("application/json")),
+                ))
+            })
+            .map_err(DrupalkitError::Encode);
+
+        self
+    }
+
+    /// Send the given raw body, without setting a `Content-Type` header.
+    pub fn body(mut self, body: impl Into<Body>) -> Self {
+        self.body = Ok(Some((body.into(), None)));
+
+        self
+    }
+
+    /// Add an option to the request.
+    ///
+    /// A `Content-Type` header set with `HttpRequestOption::Header`
+    /// takes precedence over the one set by `json`.
+    pub fn option(mut self, option: HttpRequestOption) -> Self {
+        self.options.push(option);
+
+        self
+    }
+
+    /// Send the request.
+    pub async fn send(self) -> Result<Response, DrupalkitError> {
+        let (drupalkit, method, path, body, options) = self.into_parts()?;
+
+        drupalkit.request(method, &path, body, options).await
+    }
+
+    /// Send the request and deserialize the JSON response body.
+    ///
+    /// Fails with `DrupalkitError::Status` if the response
+    /// has a non-success status code.
+    pub async fn send_json<T>(self) -> Result<T, DrupalkitError>
+    where
+        T: DeserializeOwned,
+    {
+        let (drupalkit, method, path, body, options) = self.into_parts()?;
+
+        execute_json(drupalkit, method, &path, body, options)
+            .await
+            .map(DrupalResponse::into_data)
+    }
+
+    /// The same as `send_json` but also returns the response metadata,
+    /// like the status, headers and the cache tags, contexts and max-age.
+    pub async fn send_json_with_meta<T>(self) -> Result<DrupalResponse<T>, DrupalkitError>
+    where
+        T: DeserializeOwned,
+    {
+        let (drupalkit, method, path, body, options) = self.into_parts()?;
+
+        execute_json(drupalkit, method, &path, body, options).await
+    }
+
+    /// Send the request and discard the response body,
+    /// e.g. for `204 No Content` responses.
+    ///
+    /// Fails with `DrupalkitError::Status` if the response
+    /// has a non-success status code.
+    pub async fn send_empty(self) -> Result<(), DrupalkitError> {
+        let response = self.send().await?;
+
+        if !response.status().is_success() {
+            return Err(FailedRequestError::from_response(response).await?.into());
+        }
+
+        Ok(())
+    }
+
+    fn into_parts(
+        self,
+    ) -> Result<(&'a Drupalkit, Method, String, Body, Vec<HttpRequestOption>), DrupalkitError> {
+        let mut options = self.options;
+
+        let body = match self.body? {
+            Some((body, content_type)) => {
+                let has_content_type = options.iter().any(|option| {
+                    matches!(option, HttpRequestOption::Header(name, _) if name == header::CONTENT_TYPE)
+                });
+
+                if let (Some(content_type), false) = (content_type, has_content_type) {
+                    options.push(HttpRequestOption::Header(
+                        header::CONTENT_TYPE,
+                        content_type,
+                    ));
+                }
+
+                body
+            }
+            None => Body::empty(),
+        };
+
+        Ok((self.drupalkit, self.method, self.path, body, options))
+    }
+}
+
+impl Drupalkit {
+    /// Build a `GET` request.
+    pub fn get(&self, path: &str) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, Method::GET, path)
+    }
+
+    /// Build a `POST` request.
+    pub fn post(&self, path: &str) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, Method::POST, path)
+    }
+
+    /// Build a `PATCH` request.
+    pub fn patch(&self, path: &str) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, Method::PATCH, path)
+    }
+
+    /// Build a `DELETE` request.
+    pub fn delete(&self, path: &str) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, Method::DELETE, path)
+    }
+}
@@ -0,0 +1,122 @@
+use http::{HeaderMap, StatusCode};
+use url::Url;
+
+use crate::{
+    cache::{self, CacheStatus, ClientCacheStatus, DrupalCacheStatus},
+    transport::{Response, ResponseExt},
+};
+
+/// A deserialized response body together with
+/// the response metadata, as returned by `request_json_with_meta`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use drupal_kit::http_client::{DrupalResponse, HttpClient};
+/// use drupal_kit::Drupalkit;
+/// use http::Method;
+///
+/// # async fn run(drupalkit: Drupalkit) -> Result<(), drupal_kit::DrupalkitError> {
+/// let response: DrupalResponse<serde_json::Value> = drupalkit
+///     .request_json_with_meta(Method::GET, "/jsonapi/node/article", "", vec![])
+///     .await?;
+///
+/// println!("cache tags: {:?}", response.cache_tags);
+/// println!("articles: {}", response.data["data"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DrupalResponse<T> {
+    /// The deserialized response body.
+    pub data: T,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    /// The URL of the response, after following redirects.
+    pub url: Option<Url>,
+    /// The cache tags of the `X-Drupal-Cache-Tags` header.
+    pub cache_tags: Vec<String>,
+    /// The cache contexts of the `X-Drupal-Cache-Contexts` header.
+    pub cache_contexts: Vec<String>,
+    /// The max-age of the `X-Drupal-Cache-Max-Age` header,
+    /// or of the `Cache-Control` header if Drupal does not send it.
+    pub max_age: Option<CacheMaxAge>,
+    /// Status of Drupal's internal page cache.
+    pub page_cache: Option<DrupalCacheStatus>,
+    /// Status of Drupal's dynamic page cache.
+    pub dynamic_page_cache: Option<DrupalCacheStatus>,
+    /// How the response cache of `Drupalkit` handled the request,
+    /// if a cache store is configured.
+    pub client_cache: Option<ClientCacheStatus>,
+}
+
+impl DrupalResponse<()> {
+    /// Collects the metadata of the response, before its body is read.
+    pub(crate) fn metadata(response: &Response) -> Self {
+        let headers = response.headers();
+
+        Self {
+            data: (),
+            status: response.status(),
+            headers: headers.clone(),
+            url: response.url().cloned(),
+            cache_tags: cache::cache_tags(headers),
+            cache_contexts: cache::space_separated(headers, "x-drupal-cache-contexts"),
+            max_age: CacheMaxAge::from_headers(headers),
+            page_cache: DrupalCacheStatus::from_header(headers, "x-drupal-cache"),
+            dynamic_page_cache: DrupalCacheStatus::from_header(headers, "x-drupal-dynamic-cache"),
+            client_cache: response
+                .extensions()
+                .get::<CacheStatus>()
+                .map(|status| status.client),
+        }
+    }
+}
+
+impl<T> DrupalResponse<T> {
+    /// Maps the payload, keeping the metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> DrupalResponse<U> {
+        DrupalResponse {
+            data: f(self.data),
+            status: self.status,
+            headers: self.headers,
+            url: self.url,
+            cache_tags: self.cache_tags,
+            cache_contexts: self.cache_contexts,
+            max_age: self.max_age,
+            page_cache: self.page_cache,
+            dynamic_page_cache: self.dynamic_page_cache,
+            client_cache: self.client_cache,
+        }
+    }
+
+    /// Returns the payload, dropping the metadata.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+/// The cacheability max-age of a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMaxAge {
+    /// The response may be cached until one of its cache tags is invalidated.
+    Permanent,
+    Seconds(u64),
+}
+
+impl CacheMaxAge {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let drupal_max_age = headers
+            .get("x-drupal-cache-max-age")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split_whitespace().next())
+            .and_then(|value| value.parse::<i64>().ok());
+
+        match drupal_max_age {
+            // Drupal sends `-1 (Permanent)` for `Cache::PERMANENT`.
+            Some(seconds) if seconds < 0 => Some(Self::Permanent),
+            Some(seconds) => Some(Self::Seconds(seconds as u64)),
+            None => cache::directive_value(headers, "max-age").map(Self::Seconds),
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime},
+};
+
+use http::{header, HeaderMap, Method, StatusCode};
+
+use crate::{
+    transport::{self, Request, Response, Transport},
+    DrupalkitError, RateLimiter,
+};
+
+pub(crate) const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+pub(crate) const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// The status codes retried by default, also used by
+/// `DrupalkitError::is_retryable`.
+pub(crate) const RETRYABLE_STATUSES: [StatusCode; 5] = [
+    StatusCode::REQUEST_TIMEOUT,
+    StatusCode::TOO_MANY_REQUESTS,
+    StatusCode::BAD_GATEWAY,
+    StatusCode::SERVICE_UNAVAILABLE,
+    StatusCode::GATEWAY_TIMEOUT,
+];
+
+/// Defines if and how failed requests are retried.
+///
+/// Requests are retried on connection errors, timeouts and responses
+/// with one of the retryable status codes. The delay between attempts grows
+/// exponentially and is overridden by the `Retry-After` header, if present.
+///
+/// By default only idempotent requests (`GET`, `HEAD`, `OPTIONS`, `TRACE`,
+/// `PUT` and `DELETE`) are retried.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "reqwest")] {
+/// use std::time::Duration;
+/// use drupal_kit::{Builder, RetryPolicy};
+///
+/// let drupalkit = Builder::new()
+///     .set_base_url("https://example.com")
+///     .set_retry_policy(
+///         RetryPolicy::new()
+///             .set_max_attempts(5)
+///             .set_backoff(Duration::from_millis(500), Duration::from_secs(30)),
+///     )
+///     .build();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retryable_statuses: Vec<StatusCode>,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            jitter: true,
+            retryable_statuses: RETRYABLE_STATUSES.to_vec(),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of attempts, including the first one.
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+
+        self
+    }
+
+    /// Sets the delay before the first retry and the upper bound of the delay.
+    ///
+    /// A `Retry-After` header exceeding `max` stops retrying.
+    pub fn set_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+
+        self
+    }
+
+    /// Enables or disables randomizing the delay between attempts.
+    pub fn set_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+
+        self
+    }
+
+    /// Sets the response status codes that trigger a retry.
+    pub fn set_retryable_statuses(mut self, statuses: Vec<StatusCode>) -> Self {
+        self.retryable_statuses = statuses;
+
+        self
+    }
+
+    /// Allows retrying non-idempotent requests like `POST` and `PATCH`.
+    pub fn set_retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Checks whether requests with the given method may be retried.
+    pub fn allows_method(&self, method: &Method) -> bool {
+        self.retry_non_idempotent
+            || matches!(
+                *method,
+                Method::GET
+                    | Method::HEAD
+                    | Method::OPTIONS
+                    | Method::TRACE
+                    | Method::PUT
+                    | Method::DELETE
+            )
+    }
+
+    /// Returns the delay before the next attempt if the given
+    /// response should be retried.
+    pub(crate) fn delay_for_response(&self, attempt: u32, response: &Response) -> Option<Duration> {
+        if !self.retryable_statuses.contains(&response.status()) {
+            return None;
+        }
+
+        match retry_after(response.headers()) {
+            Some(delay) if delay > self.max_backoff => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Returns the delay before the next attempt if the given
+    /// error should be retried.
+    pub(crate) fn delay_for_error(&self, attempt: u32, err: &DrupalkitError) -> Option<Duration> {
+        if err.is_retryable() {
+            Some(self.backoff(attempt))
+        } else {
+            None
+        }
+    }
+
+    /// Computes the exponential backoff for the given attempt.
+    ///
+    /// With jitter enabled the delay is randomized between
+    /// half and the full computed delay.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        if !self.jitter {
+            return delay;
+        }
+
+        let half = delay / 2;
+        let random = RandomState::new().build_hasher().finish();
+        let jitter = half.mul_f64(random as f64 / u64::MAX as f64);
+
+        half + jitter
+    }
+}
+
+/// Parses the `Retry-After` header, given either in seconds or as a HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Executes the request, retrying it according to the given policy.
+///
+/// Each attempt waits for the rate limiter, so retries
+/// are limited the same as the first attempt.
+///
+/// Streaming request bodies are buffered in memory before the first
+/// attempt, so the request can be replayed.
+pub(crate) async fn execute(
+    transport: &dyn Transport,
+    mut req: Request,
+    policy: Option<RetryPolicy>,
+    rate_limiter: Option<&RateLimiter>,
+    path: &str,
+) -> Result<Response, DrupalkitError> {
+    let policy = match policy {
+        Some(policy) if policy.max_attempts > 1 && policy.allows_method(req.method()) => policy,
+        _ => return send(transport, req, rate_limiter, path).await,
+    };
+
+    buffer_body(&mut req).await?;
+
+    let mut attempt = 1;
+
+    loop {
+        let next_req = if attempt < policy.max_attempts {
+            transport::try_clone_request(&req)
+        } else {
+            None
+        };
+
+        let result = send(transport, req, rate_limiter, path).await;
+
+        let Some(next_req) = next_req else {
+            return result;
+        };
+
+        let delay = match &result {
+            Ok(response) => policy.delay_for_response(attempt, response),
+            Err(err) => policy.delay_for_error(attempt, err),
+        };
+
+        let Some(delay) = delay else {
+            return result;
+        };
+
+        tokio::time::sleep(delay).await;
+
+        req = next_req;
+        attempt += 1;
+
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("retries", attempt - 1);
+    }
+}
+
+/// Sends a single attempt, once the rate limit allows it.
+async fn send(
+    transport: &dyn Transport,
+    req: Request,
+    rate_limiter: Option<&RateLimiter>,
+    path: &str,
+) -> Result<Response, DrupalkitError> {
+    if let Some(rate_limiter) = rate_limiter {
+        rate_limiter.acquire(path).await;
+    }
+
+    transport::send(transport, req).await
+}
+
+/// Replaces a streaming request body with its buffered content.
+async fn buffer_body(req: &mut Request) -> Result<(), DrupalkitError> {
+    if req.body().as_bytes().is_some() {
+        return Ok(());
+    }
+
+    let body = std::mem::take(req.body_mut());
+    *req.body_mut() = body.bytes().await?.into();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_without_jitter() {
+        let policy = RetryPolicy::new()
+            .set_backoff(Duration::from_millis(100), Duration::from_millis(350))
+            .set_jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_backoff_with_jitter() {
+        let policy =
+            RetryPolicy::new().set_backoff(Duration::from_secs(4), Duration::from_secs(60));
+
+        for _ in 0..100 {
+            let delay = policy.backoff(1);
+
+            assert!(delay >= Duration::from_secs(2));
+            assert!(delay <= Duration::from_secs(4));
+        }
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(header::RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(
+            header::RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_allows_method() {
+        let policy = RetryPolicy::new();
+
+        assert!(policy.allows_method(&Method::GET));
+        assert!(policy.allows_method(&Method::DELETE));
+        assert!(!policy.allows_method(&Method::POST));
+        assert!(!policy.allows_method(&Method::PATCH));
+
+        let policy = policy.set_retry_non_idempotent(true);
+
+        assert!(policy.allows_method(&Method::POST));
+    }
+}
@@ -0,0 +1,106 @@
+use core::fmt;
+
+use serde::{Deserialize, Deserializer};
+use zeroize::Zeroize;
+
+/// A credential like a password, client secret or access token.
+///
+/// The value is never printed by `Debug` or `Display` and
+/// is overwritten with zeros when dropped. It can only be
+/// read using `expose_secret`.
+///
+/// The request bodies and header values built from a secret by this
+/// crate are zeroized as well. Copies made by `http::HeaderValue` and
+/// the HTTP client while sending a request are out of reach though,
+/// so they are not zeroized.
+///
+/// # Example
+///
+/// ```rust
+/// use drupal_kit::Secret;
+///
+/// let password = Secret::new("hunter2");
+///
+/// assert_eq!(format!("{:?}", password), "[REDACTED]");
+/// assert_eq!(password.expose_secret(), "hunter2");
+/// ```
+#[derive(Clone)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Returns the secret value.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl From<&String> for Secret {
+    fn from(value: &String) -> Self {
+        Self(value.clone())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted() {
+        let secret = Secret::from("_secret_");
+
+        assert_eq!(format!("{:?}", secret), "[REDACTED]");
+        assert_eq!(secret.to_string(), "[REDACTED]");
+        assert_eq!(format!("{:?}", Some(secret.clone())), "Some([REDACTED])");
+        assert_eq!(secret.expose_secret(), "_secret_");
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let secret: Secret = serde_json::from_str(r#""_secret_""#).unwrap();
+
+        assert_eq!(secret.expose_secret(), "_secret_");
+    }
+}
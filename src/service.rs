@@ -0,0 +1,119 @@
+use std::{
+    error::Error,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http::Method;
+use tower::{util::BoxCloneSyncService, Service, ServiceExt};
+
+use crate::{
+    http_client::{self, HttpRequestOption},
+    transport::{Body, Response},
+    Drupalkit, DrupalkitError,
+};
+
+/// The type erased service the layers registered with
+/// `DrupalkitBuilder::with_layer` are applied to.
+pub type DrupalService = BoxCloneSyncService<DrupalRequest, Response, DrupalkitError>;
+
+/// A request passed through the `tower` layer stack of `Drupalkit`.
+///
+/// The fields correspond to the arguments of `HttpClient::request`.
+pub struct DrupalRequest {
+    pub method: Method,
+    pub path: String,
+    pub body: Body,
+    pub options: Vec<HttpRequestOption>,
+
+    drupalkit: Option<Drupalkit>,
+}
+
+impl DrupalRequest {
+    pub fn new(
+        method: Method,
+        path: &str,
+        body: impl Into<Body>,
+        options: Vec<HttpRequestOption>,
+    ) -> Self {
+        Self {
+            method,
+            path: path.to_owned(),
+            body: body.into(),
+            options,
+            drupalkit: None,
+        }
+    }
+}
+
+/// The innermost service, running the request flow
+/// of the `Drupalkit` that dispatched the request.
+#[derive(Clone)]
+pub(crate) struct RequestService;
+
+impl Service<DrupalRequest> for RequestService {
+    type Response = Response;
+    type Error = DrupalkitError;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, DrupalkitError>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: DrupalRequest) -> Self::Future {
+        Box::pin(async move {
+            let drupalkit = req.drupalkit.ok_or_else(|| {
+                DrupalkitError::Config("request was not dispatched by drupalkit".to_owned())
+            })?;
+
+            http_client::execute(&drupalkit, req.method, &req.path, req.body, req.options).await
+        })
+    }
+}
+
+/// Converts the error of a layer back into a `DrupalkitError`.
+pub(crate) fn into_drupalkit_error(err: Box<dyn Error + Send + Sync>) -> DrupalkitError {
+    match err.downcast::<DrupalkitError>() {
+        Ok(err) => *err,
+        Err(err) => DrupalkitError::Middleware(err),
+    }
+}
+
+impl Drupalkit {
+    /// Passes the request through the layer stack, if any.
+    pub(crate) async fn dispatch(
+        &self,
+        mut req: DrupalRequest,
+    ) -> Result<Response, DrupalkitError> {
+        match &self.service {
+            Some(service) => {
+                req.drupalkit = Some(self.clone());
+
+                service.clone().oneshot(req).await
+            }
+            None => http_client::execute(self, req.method, &req.path, req.body, req.options).await,
+        }
+    }
+}
+
+/// Exposes `Drupalkit` as a `tower::Service`.
+///
+/// Requests are passed through the layers registered with
+/// `DrupalkitBuilder::with_layer`, the same way as requests
+/// made with `HttpClient::request`.
+impl Service<DrupalRequest> for Drupalkit {
+    type Response = Response;
+    type Error = DrupalkitError;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, DrupalkitError>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: DrupalRequest) -> Self::Future {
+        let drupalkit = self.clone();
+
+        Box::pin(async move { drupalkit.dispatch(req).await })
+    }
+}
@@ -1,11 +1,13 @@
 use chrono::{DateTime, Duration, Utc};
 
+use crate::Secret;
+
 use super::drupalkit::SimpleOauthTokenResponse;
 
 #[derive(Debug, Clone)]
 pub struct AccessToken {
     pub expires_at: DateTime<Utc>,
-    pub value: String,
+    pub value: Secret,
 }
 
 impl AccessToken {
@@ -42,7 +44,7 @@ mod tests {
 
         let access_token = AccessToken {
             expires_at,
-            value: "some-val".to_owned(),
+            value: "some-val".into(),
         };
 
         assert!(access_token.is_expired());
@@ -54,7 +56,7 @@ mod tests {
 
         let access_token = AccessToken {
             expires_at,
-            value: "some-val".to_owned(),
+            value: "some-val".into(),
         };
 
         assert!(!access_token.is_expired());
@@ -66,7 +68,7 @@ mod tests {
 
         let access_token = AccessToken {
             expires_at,
-            value: "some-val".to_owned(),
+            value: "some-val".into(),
         };
 
         assert!(access_token.is_expired());
@@ -78,7 +80,7 @@ mod tests {
         let expected_expires = Utc::now() + Duration::seconds(300);
 
         let res = SimpleOauthTokenResponse {
-            access_token: value.to_owned(),
+            access_token: value.into(),
             token_type: "bearer".to_owned(),
             expires_in: 300,
             refresh_token: None,
@@ -86,7 +88,8 @@ mod tests {
 
         let access_token: AccessToken = res.into();
 
-        assert_eq!(access_token.value, value);
+        assert_eq!(access_token.value.expose_secret(), value);
+        assert!(!format!("{:?}", access_token).contains(value));
 
         // Compare the timestamp (seconds), because comparing the dates directly
         // is not equal, due to nanosecond differences in execution.
@@ -1,10 +1,10 @@
 use async_trait::async_trait;
-use reqwest::RequestBuilder;
+use http::{header, request::Parts};
 
 use crate::{
-    auth::{AuthStrategy, AuthStrategyError, AuthStrategyResult},
+    auth::{bearer_auth, AuthStrategy, AuthStrategyError, AuthStrategyResult},
     http_client::HttpRequestOption,
-    Drupalkit,
+    Drupalkit, Secret,
 };
 
 use super::{AccessToken, SimpleOauthGrant};
@@ -13,16 +13,16 @@ pub struct ClientCredentialsAuthStrategy {
     access_token: Option<AccessToken>,
 
     client_id: String,
-    client_secret: String,
+    client_secret: Secret,
     scopes: Vec<String>,
 }
 
 impl ClientCredentialsAuthStrategy {
-    pub fn new(client_id: &str, client_secret: &str, scopes: Vec<String>) -> Self {
+    pub fn new(client_id: &str, client_secret: impl Into<Secret>, scopes: Vec<String>) -> Self {
         Self {
             access_token: None,
             client_id: client_id.to_owned(),
-            client_secret: client_secret.to_owned(),
+            client_secret: client_secret.into(),
             scopes,
         }
     }
@@ -32,7 +32,7 @@ impl ClientCredentialsAuthStrategy {
 impl AuthStrategy for ClientCredentialsAuthStrategy {
     async fn set_auth_info(
         &mut self,
-        req_builder: RequestBuilder,
+        parts: &mut Parts,
         _path: &str,
         _options: Vec<HttpRequestOption>,
         drupalkit: &Drupalkit,
@@ -40,7 +40,15 @@ impl AuthStrategy for ClientCredentialsAuthStrategy {
         // Check if cached access token exists and is still valid.
         if let Some(access_token) = &self.access_token {
             if !access_token.is_expired() {
-                return Ok(req_builder.bearer_auth(access_token.value.clone()));
+                #[cfg(feature = "tracing")]
+                crate::trace::record_auth("cached_token");
+
+                parts.headers.insert(
+                    header::AUTHORIZATION,
+                    bearer_auth(access_token.value.expose_secret())?,
+                );
+
+                return Ok(());
             }
         }
 
@@ -53,11 +61,32 @@ impl AuthStrategy for ClientCredentialsAuthStrategy {
 
         match drupalkit.request_token(grant).await {
             Ok(res) => {
-                self.access_token = Some(res.clone().into());
+                #[cfg(feature = "tracing")]
+                crate::trace::record_auth("fresh_grant");
+
+                parts.headers.insert(
+                    header::AUTHORIZATION,
+                    bearer_auth(res.access_token.expose_secret())?,
+                );
 
-                Ok(req_builder.bearer_auth(res.access_token))
+                self.access_token = Some(res.into());
+
+                Ok(())
             }
-            Err(err) => Err(AuthStrategyError::new(err)),
+            Err(err) => Err(AuthStrategyError::new(Box::new(err)).into()),
         }
     }
+
+    async fn on_unauthorized(
+        &mut self,
+        _path: &str,
+        _options: Vec<HttpRequestOption>,
+        _drupalkit: &Drupalkit,
+    ) -> bool {
+        // The token might have been revoked before it expired,
+        // e.g. because the consumer secret was rotated.
+        self.access_token = None;
+
+        true
+    }
 }
@@ -1,9 +1,11 @@
 use std::collections::BTreeMap;
 
+use bytes::Bytes;
 use http::{header, HeaderValue, Method};
 use serde::Deserialize;
+use zeroize::Zeroizing;
 
-use crate::{http_client::*, Drupalkit};
+use crate::{http_client::*, Drupalkit, Secret};
 
 use super::grant::SimpleOauthGrant;
 
@@ -11,74 +13,100 @@ use super::grant::SimpleOauthGrant;
 pub struct SimpleOauthTokenResponse {
     pub token_type: String,
     pub expires_in: u32,
-    pub access_token: String,
-    pub refresh_token: Option<String>,
+    pub access_token: Secret,
+    pub refresh_token: Option<Secret>,
 }
 
 impl Drupalkit {
+    /// Requests an access token using the given grant.
+    ///
+    /// With the `tracing` feature the request is recorded in a span
+    /// with the grant type only, the credentials are never recorded.
     pub async fn request_token(
         &self,
         grant: SimpleOauthGrant,
-    ) -> Result<SimpleOauthTokenResponse, ClientError> {
+    ) -> Result<SimpleOauthTokenResponse, DrupalkitError> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("drupalkit.request_token", grant_type = grant.grant_type(),);
+
+        let scopes;
         let mut body_parts = BTreeMap::new();
 
-        match grant {
+        // Borrow the credentials, so they are not copied into
+        // buffers which are not zeroized when dropped.
+        match &grant {
             SimpleOauthGrant::ClientCredentials {
                 client_id,
                 client_secret,
-                scopes,
+                scopes: grant_scopes,
             } => {
-                body_parts.insert("grant_type", "client_credentials".to_owned());
-                body_parts.insert("client_id", client_id);
-                body_parts.insert("client_secret", client_secret);
-                body_parts.insert("scopes", scopes.join(","));
+                scopes = grant_scopes.join(",");
+
+                body_parts.insert("grant_type", "client_credentials");
+                body_parts.insert("client_id", client_id.as_str());
+                body_parts.insert("client_secret", client_secret.expose_secret());
+                body_parts.insert("scopes", scopes.as_str());
             }
             SimpleOauthGrant::RefreshToken {
                 client_id,
                 client_secret,
                 refresh_token,
-                scopes,
+                scopes: grant_scopes,
             } => {
-                body_parts.insert("grant_type", "refresh_token".to_owned());
-                body_parts.insert("client_id", client_id);
-                body_parts.insert("client_secret", client_secret);
-                body_parts.insert("refresh_token", refresh_token);
-                body_parts.insert("scopes", scopes.join(","));
+                scopes = grant_scopes.join(",");
+
+                body_parts.insert("grant_type", "refresh_token");
+                body_parts.insert("client_id", client_id.as_str());
+                body_parts.insert("client_secret", client_secret.expose_secret());
+                body_parts.insert("refresh_token", refresh_token.expose_secret());
+                body_parts.insert("scopes", scopes.as_str());
             }
         };
 
-        let mut kv_pairs = Vec::new();
+        let len = body_parts
+            .iter()
+            .map(|(key, val)| key.len() + val.len() + 2)
+            .sum();
+
+        // Create full body for use as application/x-www-form-urlencoded.
+        let mut body = Zeroizing::new(Vec::with_capacity(len));
 
-        // Create query param pairs.
         for (key, val) in body_parts.iter() {
             if val.is_empty() {
                 continue;
             }
 
-            kv_pairs.push(format!("{}={}", key, val));
+            if !body.is_empty() {
+                body.push(b'&');
+            }
+
+            body.extend_from_slice(key.as_bytes());
+            body.push(b'=');
+            body.extend_from_slice(val.as_bytes());
         }
 
-        // Create full body for use as application/x-www-form-urlencoded.
-        let body = kv_pairs.join("&");
-
-        let res = self
-            .request_json::<SimpleOauthTokenResponse>(
-                Method::POST,
-                "/oauth/token",
-                body,
-                // This MUST be an anonymous request, otherwise auth strategies would introduce
-                // stack overflows by calling the `request_token` method itself leading to an
-                // infinite loop!
-                vec![
-                    HttpRequestOption::Anonymous,
-                    HttpRequestOption::Header(
-                        header::CONTENT_TYPE.to_owned(),
-                        HeaderValue::from_str("application/x-www-form-urlencoded")?,
-                    ),
-                ],
-            )
-            .await?;
-
-        Ok(res)
+        // The body is zeroized once the request was sent.
+        let body = Bytes::from_owner(body);
+
+        let future = self.request_json::<SimpleOauthTokenResponse>(
+            Method::POST,
+            "/oauth/token",
+            body,
+            // This MUST be an anonymous request, otherwise auth strategies would introduce
+            // stack overflows by calling the `request_token` method itself leading to an
+            // infinite loop!
+            vec![
+                HttpRequestOption::Anonymous,
+                HttpRequestOption::Header(
+                    header::CONTENT_TYPE.to_owned(),
+                    HeaderValue::from_static("application/x-www-form-urlencoded"),
+                ),
+            ],
+        );
+
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+
+        future.await
     }
 }
@@ -1,13 +1,25 @@
+use crate::Secret;
+
 pub enum SimpleOauthGrant {
     ClientCredentials {
         client_id: String,
-        client_secret: String,
+        client_secret: Secret,
         scopes: Vec<String>,
     },
     RefreshToken {
         client_id: String,
-        client_secret: String,
-        refresh_token: String,
+        client_secret: Secret,
+        refresh_token: Secret,
         scopes: Vec<String>,
     },
 }
+
+impl SimpleOauthGrant {
+    /// Returns the value of the `grant_type` parameter.
+    pub fn grant_type(&self) -> &'static str {
+        match self {
+            Self::ClientCredentials { .. } => "client_credentials",
+            Self::RefreshToken { .. } => "refresh_token",
+        }
+    }
+}
@@ -0,0 +1,402 @@
+//! Record and replay transports for deterministic tests.
+//!
+//! Record the requests of a test against a real Drupal site once using
+//! `RecordingTransport`, then run the test offline using `ReplayTransport`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use drupal_kit::testing::{RecordingTransport, ReplayTransport};
+//! use drupal_kit::transport::ReqwestTransport;
+//! use drupal_kit::Builder;
+//!
+//! # fn run() -> Result<(), drupal_kit::DrupalkitError> {
+//! let fixture = "tests/fixtures/articles.json";
+//!
+//! let builder = Builder::new().set_base_url("https://example.com");
+//!
+//! let drupalkit = match std::env::var("RECORD").is_ok() {
+//!     true => builder.set_transport(RecordingTransport::new(ReqwestTransport::default(), fixture)),
+//!     false => builder.set_transport(ReplayTransport::from_file(fixture)?),
+//! }
+//! .build();
+//! # Ok(())
+//! # }
+//! ```
+
+mod record;
+mod replay;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use bytes::Bytes;
+use http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::DrupalkitError;
+
+pub use record::RecordingTransport;
+pub use replay::ReplayTransport;
+
+/// Replaces the values of scrubbed headers and fields.
+const REDACTED: &str = "[REDACTED]";
+
+/// Headers scrubbed by default.
+const SCRUBBED_HEADERS: [&str; 5] = [
+    "authorization",
+    "cookie",
+    "set-cookie",
+    "x-csrf-token",
+    "proxy-authorization",
+];
+
+/// Body and query fields scrubbed by default.
+const SCRUBBED_FIELDS: [&str; 8] = [
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "password",
+    "pass",
+    "csrf_token",
+    "logout_token",
+    "token",
+];
+
+/// The recorded interactions stored in a fixture file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Fixture {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedRequest {
+    #[serde(with = "method")]
+    method: Method,
+    path: String,
+    #[serde(default)]
+    query: Vec<(String, String)>,
+    #[serde(default)]
+    body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+    #[serde(default)]
+    body: String,
+    /// Whether the body is base64 encoded, as it is not valid UTF-8.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    base64: bool,
+}
+
+impl RecordedResponse {
+    fn new(status: StatusCode, headers: &HeaderMap, body: &[u8], scrubber: &Scrubber) -> Self {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let value = scrubber.scrub_header(name, &String::from_utf8_lossy(value.as_bytes()));
+
+                (name.to_string(), value)
+            })
+            .collect();
+
+        let (body, base64) = match std::str::from_utf8(body) {
+            Ok(body) => (scrubber.scrub_body(body), false),
+            Err(_) => (BASE64_STANDARD.encode(body), true),
+        };
+
+        Self {
+            status: status.as_u16(),
+            headers,
+            body,
+            base64,
+        }
+    }
+
+    fn to_response(&self) -> Result<crate::transport::Response, DrupalkitError> {
+        let body = match self.base64 {
+            true => BASE64_STANDARD
+                .decode(&self.body)
+                .map_err(|err| DrupalkitError::Config(format!("invalid fixture body: {}", err)))?
+                .into(),
+            false => Bytes::from(self.body.clone()),
+        };
+
+        let mut builder = http::Response::builder().status(self.status);
+
+        for (name, value) in &self.headers {
+            builder = builder.header(
+                HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|err| DrupalkitError::Config(err.to_string()))?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+
+        Ok(builder.body(body.into())?)
+    }
+}
+
+/// Removes secrets from recorded requests and responses.
+#[derive(Debug, Clone)]
+struct Scrubber {
+    headers: Vec<HeaderName>,
+    fields: Vec<String>,
+}
+
+impl Default for Scrubber {
+    fn default() -> Self {
+        Self {
+            headers: SCRUBBED_HEADERS
+                .iter()
+                .map(|name| HeaderName::from_static(name))
+                .collect(),
+            fields: SCRUBBED_FIELDS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Scrubber {
+    fn is_scrubbed_header(&self, name: &HeaderName) -> bool {
+        self.headers.contains(name)
+    }
+
+    /// Scrubs the value of a header.
+    ///
+    /// Only the cookie values of `Cookie` and `Set-Cookie` are scrubbed,
+    /// their names and attributes are kept, so a recorded login still
+    /// sets a session cookie when replayed.
+    fn scrub_header(&self, name: &HeaderName, value: &str) -> String {
+        if !self.is_scrubbed_header(name) {
+            return value.to_owned();
+        }
+
+        if name == header::SET_COOKIE {
+            let (pair, attributes) = match value.split_once(';') {
+                Some((pair, attributes)) => (pair, Some(attributes)),
+                None => (value, None),
+            };
+
+            return match (pair.split_once('='), attributes) {
+                (Some((key, _)), Some(attributes)) => {
+                    format!("{}={};{}", key, REDACTED, attributes)
+                }
+                (Some((key, _)), None) => format!("{}={}", key, REDACTED),
+                (None, _) => REDACTED.to_owned(),
+            };
+        }
+
+        if name == header::COOKIE {
+            return value
+                .split(';')
+                .map(|pair| match pair.split_once('=') {
+                    Some((key, _)) => format!("{}={}", key.trim(), REDACTED),
+                    None => REDACTED.to_owned(),
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+        }
+
+        REDACTED.to_owned()
+    }
+
+    fn is_scrubbed_field(&self, name: &str) -> bool {
+        self.fields
+            .iter()
+            .any(|field| field.eq_ignore_ascii_case(name))
+    }
+
+    /// Scrubs the request the same way when recording and replaying,
+    /// so requests containing secrets still match their recording.
+    fn scrub_request(&self, method: &Method, uri: &http::Uri, body: &[u8]) -> RecordedRequest {
+        let query = uri
+            .query()
+            .map(|query| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .map(|(key, value)| {
+                        let value = match self.is_scrubbed_field(&key) {
+                            true => REDACTED.to_owned(),
+                            false => value.into_owned(),
+                        };
+
+                        (key.into_owned(), value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        RecordedRequest {
+            method: method.clone(),
+            path: uri.path().to_owned(),
+            query,
+            body: self.scrub_body(&String::from_utf8_lossy(body)),
+        }
+    }
+
+    /// Scrubs the fields of a JSON or form encoded body.
+    fn scrub_body(&self, body: &str) -> String {
+        if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(body) {
+            return match self.scrub_json(&mut value) {
+                true => value.to_string(),
+                false => body.to_owned(),
+            };
+        }
+
+        let is_form = !body.is_empty()
+            && !body.contains(char::is_whitespace)
+            && body.split('&').all(|pair| pair.contains('='));
+
+        if !is_form {
+            return body.to_owned();
+        }
+
+        let mut scrubbed = false;
+
+        let pairs: Vec<String> = body
+            .split('&')
+            .map(|pair| {
+                let (key, _) = pair.split_once('=').unwrap_or((pair, ""));
+
+                match self.is_scrubbed_field(key) {
+                    true => {
+                        scrubbed = true;
+
+                        format!("{}={}", key, REDACTED)
+                    }
+                    false => pair.to_owned(),
+                }
+            })
+            .collect();
+
+        match scrubbed {
+            true => pairs.join("&"),
+            false => body.to_owned(),
+        }
+    }
+
+    /// Scrubs the fields of a JSON value and returns whether any was scrubbed.
+    fn scrub_json(&self, value: &mut serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut scrubbed = false;
+
+                for (key, value) in map.iter_mut() {
+                    if self.is_scrubbed_field(key) {
+                        *value = serde_json::Value::String(REDACTED.to_owned());
+                        scrubbed = true;
+                    } else {
+                        scrubbed |= self.scrub_json(value);
+                    }
+                }
+
+                scrubbed
+            }
+            serde_json::Value::Array(values) => values
+                .iter_mut()
+                .fold(false, |scrubbed, value| self.scrub_json(value) || scrubbed),
+            _ => false,
+        }
+    }
+}
+
+/// (De)serializes a `Method` as string.
+mod method {
+    use http::Method;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(method: &Method, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(method.as_str())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Method, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let method = String::deserialize(deserializer)?;
+
+        Method::from_bytes(method.as_bytes()).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_json_body() {
+        let scrubber = Scrubber::default();
+
+        assert_eq!(
+            scrubber.scrub_body(r#"{"name":{"value":"admin"},"pass":"secret"}"#),
+            r#"{"name":{"value":"admin"},"pass":"[REDACTED]"}"#
+        );
+        assert_eq!(
+            scrubber.scrub_body(r#"{"data": {"type": "node--article"}}"#),
+            r#"{"data": {"type": "node--article"}}"#
+        );
+    }
+
+    #[test]
+    fn test_scrub_form_body() {
+        let scrubber = Scrubber::default();
+
+        assert_eq!(
+            scrubber.scrub_body("client_id=abc&client_secret=secret&grant_type=client_credentials"),
+            "client_id=abc&client_secret=[REDACTED]&grant_type=client_credentials"
+        );
+        assert_eq!(scrubber.scrub_body("plain text"), "plain text");
+    }
+
+    #[test]
+    fn test_scrub_cookie_headers() {
+        let scrubber = Scrubber::default();
+
+        assert_eq!(
+            scrubber.scrub_header(&header::SET_COOKIE, "SESSxyz=abc; path=/; HttpOnly"),
+            "SESSxyz=[REDACTED]; path=/; HttpOnly"
+        );
+        assert_eq!(
+            scrubber.scrub_header(&header::COOKIE, "SESSxyz=abc; has_js=1"),
+            "SESSxyz=[REDACTED]; has_js=[REDACTED]"
+        );
+        assert_eq!(
+            scrubber.scrub_header(&header::AUTHORIZATION, "Bearer abc"),
+            REDACTED
+        );
+        assert_eq!(
+            scrubber.scrub_header(&header::CONTENT_TYPE, "text/plain"),
+            "text/plain"
+        );
+    }
+
+    #[test]
+    fn test_scrub_query() {
+        let scrubber = Scrubber::default();
+        let uri: http::Uri = "https://example.com/jsonapi?token=secret&page=1"
+            .parse()
+            .unwrap();
+
+        let request = scrubber.scrub_request(&Method::GET, &uri, b"");
+
+        assert_eq!(request.path, "/jsonapi");
+        assert_eq!(
+            request.query,
+            vec![
+                ("token".to_owned(), REDACTED.to_owned()),
+                ("page".to_owned(), "1".to_owned()),
+            ]
+        );
+    }
+}
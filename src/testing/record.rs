@@ -0,0 +1,102 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use http::HeaderName;
+
+use crate::{
+    transport::{Body, Request, Response, Transport},
+    DrupalkitError,
+};
+
+use super::{Fixture, Interaction, RecordedResponse, Scrubber};
+
+/// Records the requests sent by an inner transport to a fixture file.
+///
+/// Every request and its response are written to the JSON fixture
+/// at `path` once the response was received, replacing a previously
+/// recorded fixture. Request and response bodies are buffered.
+///
+/// Secrets are scrubbed before writing: the values of the `Authorization`
+/// and `X-CSRF-Token` headers, the cookie values of the `Cookie` and
+/// `Set-Cookie` headers, and the values of fields like `password`,
+/// `client_secret` or `access_token` in JSON and form encoded bodies
+/// and in query strings, are replaced by `[REDACTED]`.
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    path: PathBuf,
+    scrubber: Scrubber,
+    fixture: Mutex<Fixture>,
+}
+
+impl RecordingTransport {
+    pub fn new<T>(inner: T, path: impl AsRef<Path>) -> Self
+    where
+        T: Transport,
+    {
+        Self {
+            inner: Arc::new(inner),
+            path: path.as_ref().to_owned(),
+            scrubber: Scrubber::default(),
+            fixture: Mutex::new(Fixture::default()),
+        }
+    }
+
+    /// Scrub the value of the given header in addition to the default ones.
+    pub fn with_scrubbed_header(mut self, name: HeaderName) -> Self {
+        self.scrubber.headers.push(name);
+
+        self
+    }
+
+    /// Scrub the given body and query field in addition to the default ones.
+    ///
+    /// The same fields must be scrubbed by the `ReplayTransport`,
+    /// so requests containing them match their recording.
+    pub fn with_scrubbed_field(mut self, name: &str) -> Self {
+        self.scrubber.fields.push(name.to_owned());
+
+        self
+    }
+
+    fn record(&self, interaction: Interaction) -> Result<(), DrupalkitError> {
+        let mut fixture = self.fixture.lock().unwrap();
+        fixture.interactions.push(interaction);
+
+        let json = serde_json::to_string_pretty(&*fixture)
+            .map_err(|err| DrupalkitError::Config(format!("invalid fixture: {}", err)))?;
+
+        std::fs::write(&self.path, json)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn send(&self, req: Request) -> Result<Response, DrupalkitError> {
+        let (parts, body) = req.into_parts();
+        let body = body.bytes().await?;
+
+        let request = self
+            .scrubber
+            .scrub_request(&parts.method, &parts.uri, &body);
+
+        let response = self
+            .inner
+            .send(Request::from_parts(parts, Body::from(body)))
+            .await?;
+
+        let (parts, body) = response.into_parts();
+        let body = body.bytes().await?;
+
+        self.record(Interaction {
+            request,
+            response: RecordedResponse::new(parts.status, &parts.headers, &body, &self.scrubber),
+        })?;
+
+        Ok(Response::from_parts(parts, Body::from(body)))
+    }
+}
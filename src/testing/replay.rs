@@ -0,0 +1,179 @@
+use std::{path::Path, sync::Mutex};
+
+use async_trait::async_trait;
+use http::HeaderName;
+
+use crate::{
+    error::{TransportError, TransportErrorKind},
+    transport::{Request, Response, Transport},
+    DrupalkitError,
+};
+
+use super::{Fixture, Interaction, RecordedRequest, Scrubber};
+
+/// Serves the responses of a fixture written by `RecordingTransport`.
+///
+/// Requests are matched against the recording by method, path, query
+/// and body, ignoring the host and headers, so a fixture recorded against
+/// one site can be replayed with any base url. Query parameters may be
+/// in any order and JSON bodies are compared by value.
+///
+/// Every recorded interaction is served once, in the order of the
+/// recording. A request without a matching recorded interaction fails
+/// with a `DrupalkitError::Transport` describing the request.
+pub struct ReplayTransport {
+    interactions: Vec<Interaction>,
+    scrubber: Scrubber,
+    replayed: Mutex<Vec<bool>>,
+}
+
+impl ReplayTransport {
+    /// Loads the fixture at the given path.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, DrupalkitError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)?;
+
+        Self::from_json(&json).map_err(|err| match err {
+            DrupalkitError::Config(message) => {
+                DrupalkitError::Config(format!("{}: {}", path.display(), message))
+            }
+            err => err,
+        })
+    }
+
+    /// Loads a fixture from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self, DrupalkitError> {
+        let fixture: Fixture = serde_json::from_str(json)
+            .map_err(|err| DrupalkitError::Config(format!("invalid fixture: {}", err)))?;
+
+        Ok(Self {
+            replayed: Mutex::new(vec![false; fixture.interactions.len()]),
+            interactions: fixture.interactions,
+            scrubber: Scrubber::default(),
+        })
+    }
+
+    /// Scrub the value of the given header in addition to the default ones.
+    pub fn with_scrubbed_header(mut self, name: HeaderName) -> Self {
+        self.scrubber.headers.push(name);
+
+        self
+    }
+
+    /// Scrub the given body and query field in addition to the default ones.
+    ///
+    /// Must match the fields scrubbed by the `RecordingTransport`.
+    pub fn with_scrubbed_field(mut self, name: &str) -> Self {
+        self.scrubber.fields.push(name.to_owned());
+
+        self
+    }
+
+    /// Asserts that every recorded interaction was replayed.
+    ///
+    /// # Panics
+    ///
+    /// Panics listing the interactions that were not replayed.
+    pub fn assert_all_replayed(&self) {
+        let pending: Vec<String> = self
+            .interactions
+            .iter()
+            .zip(self.replayed.lock().unwrap().iter())
+            .filter(|(_, replayed)| !**replayed)
+            .map(|(interaction, _)| describe(&interaction.request))
+            .collect();
+
+        if !pending.is_empty() {
+            panic!(
+                "recorded interactions were not replayed:\n  {}",
+                pending.join("\n  ")
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn send(&self, req: Request) -> Result<Response, DrupalkitError> {
+        let (parts, body) = req.into_parts();
+        let body = body.bytes().await?;
+
+        let request = self
+            .scrubber
+            .scrub_request(&parts.method, &parts.uri, &body);
+
+        let index = {
+            let mut replayed = self.replayed.lock().unwrap();
+
+            let index = self.interactions.iter().zip(replayed.iter()).position(
+                |(interaction, replayed)| !replayed && matches(&interaction.request, &request),
+            );
+
+            if let Some(index) = index {
+                replayed[index] = true;
+            }
+
+            index
+        };
+
+        let Some(index) = index else {
+            let message = format!("no recorded interaction matches {}", describe(&request));
+
+            return Err(TransportError::new(TransportErrorKind::Other, message).into());
+        };
+
+        let mut response = self.interactions[index].response.to_response()?;
+
+        if let Ok(url) = url::Url::parse(&parts.uri.to_string()) {
+            response.extensions_mut().insert(url);
+        }
+
+        Ok(response)
+    }
+}
+
+fn matches(recorded: &RecordedRequest, request: &RecordedRequest) -> bool {
+    let mut recorded_query = recorded.query.clone();
+    let mut query = request.query.clone();
+    recorded_query.sort();
+    query.sort();
+
+    recorded.method == request.method
+        && recorded.path == request.path
+        && recorded_query == query
+        && body_matches(&recorded.body, &request.body)
+}
+
+fn body_matches(recorded: &str, body: &str) -> bool {
+    if recorded == body {
+        return true;
+    }
+
+    match (
+        serde_json::from_str::<serde_json::Value>(recorded),
+        serde_json::from_str::<serde_json::Value>(body),
+    ) {
+        (Ok(recorded), Ok(body)) => recorded == body,
+        _ => false,
+    }
+}
+
+fn describe(request: &RecordedRequest) -> String {
+    let mut description = format!("{} {}", request.method, request.path);
+
+    if !request.query.is_empty() {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&request.query)
+            .finish();
+
+        description.push('?');
+        description.push_str(&query);
+    }
+
+    if !request.body.is_empty() {
+        description.push_str(" with body ");
+        description.push_str(&request.body);
+    }
+
+    description
+}
@@ -0,0 +1,217 @@
+use core::fmt;
+use std::{
+    error::Error,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_util::{TryStream, TryStreamExt};
+use http_body::{Frame, SizeHint};
+use http_body_util::{combinators::BoxBody, BodyDataStream, BodyExt, StreamBody};
+
+use crate::{error::TransportError, http_client::ByteStream, DrupalkitError};
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+/// The body of a request or response.
+///
+/// A body is either a buffered chunk of bytes, which can be cloned
+/// to retry or replay a request, or a stream of chunks.
+pub struct Body {
+    inner: Inner,
+}
+
+enum Inner {
+    Full(Bytes),
+    Streaming(BoxBody<Bytes, BoxError>),
+}
+
+impl Body {
+    pub fn empty() -> Self {
+        Bytes::new().into()
+    }
+
+    /// Wraps a `http_body::Body`, e.g. the response body of a HTTP client.
+    pub fn wrap<B>(body: B) -> Self
+    where
+        B: http_body::Body + Send + Sync + 'static,
+        B::Data: Into<Bytes>,
+        B::Error: Into<BoxError>,
+    {
+        let body = body
+            .map_frame(|frame| frame.map_data(Into::into))
+            .map_err(Into::into)
+            .boxed();
+
+        Self {
+            inner: Inner::Streaming(body),
+        }
+    }
+
+    /// Wraps a stream of chunks, e.g. to upload a file without buffering it.
+    pub fn wrap_stream<S>(stream: S) -> Self
+    where
+        S: TryStream + Send + Sync + 'static,
+        S::Ok: Into<Bytes>,
+        S::Error: Into<BoxError>,
+    {
+        let stream = stream
+            .map_ok(|chunk| Frame::data(chunk.into()))
+            .map_err(Into::into);
+
+        Self::wrap(StreamBody::new(stream))
+    }
+
+    /// Returns the content of a buffered body.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match &self.inner {
+            Inner::Full(bytes) => Some(bytes),
+            Inner::Streaming(_) => None,
+        }
+    }
+
+    /// Clones a buffered body. Streaming bodies can not be cloned.
+    pub fn try_clone(&self) -> Option<Self> {
+        match &self.inner {
+            Inner::Full(bytes) => Some(bytes.clone().into()),
+            Inner::Streaming(_) => None,
+        }
+    }
+
+    /// Returns the content of a buffered body, or the body itself if it is streaming.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn try_into_bytes(self) -> Result<Bytes, Self> {
+        match self.inner {
+            Inner::Full(bytes) => Ok(bytes),
+            inner => Err(Self { inner }),
+        }
+    }
+
+    /// Reads the whole body.
+    pub async fn bytes(self) -> Result<Bytes, DrupalkitError> {
+        match self.inner {
+            Inner::Full(bytes) => Ok(bytes),
+            Inner::Streaming(body) => Ok(body
+                .collect()
+                .await
+                .map_err(TransportError::from_boxed)?
+                .to_bytes()),
+        }
+    }
+
+    /// Returns the body as a stream of chunks.
+    pub fn into_stream(self) -> ByteStream {
+        let stream = BodyDataStream::new(self)
+            .map_err(|err| DrupalkitError::from(TransportError::from_boxed(err)));
+
+        Box::pin(stream)
+    }
+}
+
+impl Default for Body {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            Inner::Full(bytes) => f.debug_tuple("Body").field(bytes).finish(),
+            Inner::Streaming(_) => f.debug_tuple("Body").field(&"streaming").finish(),
+        }
+    }
+}
+
+impl http_body::Body for Body {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match &mut self.inner {
+            Inner::Full(bytes) if bytes.is_empty() => Poll::Ready(None),
+            Inner::Full(bytes) => Poll::Ready(Some(Ok(Frame::data(std::mem::take(bytes))))),
+            Inner::Streaming(body) => Pin::new(body).poll_frame(cx),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.inner {
+            Inner::Full(bytes) => bytes.is_empty(),
+            Inner::Streaming(body) => body.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.inner {
+            Inner::Full(bytes) => SizeHint::with_exact(bytes.len() as u64),
+            Inner::Streaming(body) => body.size_hint(),
+        }
+    }
+}
+
+impl From<Bytes> for Body {
+    fn from(bytes: Bytes) -> Self {
+        Self {
+            inner: Inner::Full(bytes),
+        }
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(vec: Vec<u8>) -> Self {
+        Bytes::from(vec).into()
+    }
+}
+
+impl From<String> for Body {
+    fn from(string: String) -> Self {
+        Bytes::from(string).into()
+    }
+}
+
+impl From<&'static str> for Body {
+    fn from(string: &'static str) -> Self {
+        Bytes::from_static(string.as_bytes()).into()
+    }
+}
+
+impl From<&'static [u8]> for Body {
+    fn from(slice: &'static [u8]) -> Self {
+        Bytes::from_static(slice).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{stream, StreamExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_full_body() {
+        let body = Body::from("hello");
+
+        assert_eq!(body.as_bytes(), Some(&b"hello"[..]));
+        assert_eq!(body.try_clone().unwrap().bytes().await.unwrap(), "hello");
+        assert_eq!(body.bytes().await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_body() {
+        let chunks: Vec<Result<_, std::io::Error>> = vec![Ok("hel"), Ok("lo")];
+        let body = Body::wrap_stream(stream::iter(chunks));
+
+        assert!(body.as_bytes().is_none());
+        assert!(body.try_clone().is_none());
+
+        let chunks: Vec<_> = body.into_stream().collect().await;
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].as_ref().unwrap(), "lo");
+    }
+}
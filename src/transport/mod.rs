@@ -0,0 +1,155 @@
+mod body;
+#[cfg(feature = "reqwest")]
+mod reqwest_transport;
+
+use std::future::Future;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::header;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use crate::{http_client::ByteStream, DrupalkitError};
+
+pub use crate::error::{TransportError, TransportErrorKind};
+pub use body::Body;
+#[cfg(feature = "reqwest")]
+pub use reqwest_transport::ReqwestTransport;
+
+pub type Request = http::Request<Body>;
+pub type Response = http::Response<Body>;
+
+/// Sends requests over the wire.
+///
+/// `Drupalkit` is not tied to a specific HTTP client. Implement this
+/// to use another client, e.g. `hyper`, or to return canned responses
+/// in unit tests. With the `reqwest` feature, enabled by default,
+/// `ReqwestTransport` is used unless another transport is configured.
+///
+/// The request URI is always absolute.
+///
+/// # Example
+///
+/// ```rust
+/// use async_trait::async_trait;
+/// use drupal_kit::transport::{Request, Response, Transport};
+/// use drupal_kit::{Builder, DrupalkitError};
+///
+/// struct NotFoundTransport;
+///
+/// #[async_trait]
+/// impl Transport for NotFoundTransport {
+///     async fn send(&self, _req: Request) -> Result<Response, DrupalkitError> {
+///         let response = http::Response::builder()
+///             .status(404)
+///             .body("Not found".into())?;
+///
+///         Ok(response)
+///     }
+/// }
+///
+/// let drupalkit = Builder::new()
+///     .set_base_url("https://example.com")
+///     .set_transport(NotFoundTransport)
+///     .build();
+/// ```
+#[async_trait]
+pub trait Transport
+where
+    Self: Send + Sync + 'static,
+{
+    async fn send(&self, req: Request) -> Result<Response, DrupalkitError>;
+}
+
+/// Sends the request using the transport and records the URL
+/// of the response, unless the transport did already.
+pub(crate) async fn send(
+    transport: &dyn Transport,
+    req: Request,
+) -> Result<Response, DrupalkitError> {
+    let url = Url::parse(&req.uri().to_string()).ok();
+    let mut response = transport.send(req).await?;
+
+    if let Some(url) = url {
+        if response.extensions().get::<Url>().is_none() {
+            response.extensions_mut().insert(url);
+        }
+    }
+
+    Ok(response)
+}
+
+/// Clones a request with a buffered body.
+pub(crate) fn try_clone_request(req: &Request) -> Option<Request> {
+    let body = req.body().try_clone()?;
+
+    let mut clone = http::Request::new(body);
+    *clone.method_mut() = req.method().clone();
+    *clone.uri_mut() = req.uri().clone();
+    *clone.version_mut() = req.version();
+    *clone.headers_mut() = req.headers().clone();
+    *clone.extensions_mut() = req.extensions().clone();
+
+    Some(clone)
+}
+
+/// Convenience methods to read a `Response`.
+pub trait ResponseExt {
+    /// Returns the URL of the response, after following redirects.
+    fn url(&self) -> Option<&Url>;
+
+    /// Returns the `Content-Length` of the response, if known.
+    fn content_length(&self) -> Option<u64>;
+
+    /// Reads the whole response body.
+    fn bytes(self) -> impl Future<Output = Result<Bytes, DrupalkitError>> + Send;
+
+    /// Reads the whole response body as UTF-8 text,
+    /// replacing invalid characters.
+    fn text(self) -> impl Future<Output = Result<String, DrupalkitError>> + Send;
+
+    /// Reads the whole response body and deserializes it as JSON.
+    fn json<T>(self) -> impl Future<Output = Result<T, DrupalkitError>> + Send
+    where
+        T: DeserializeOwned;
+
+    /// Returns the response body as a stream of chunks.
+    fn bytes_stream(self) -> ByteStream;
+}
+
+impl ResponseExt for Response {
+    fn url(&self) -> Option<&Url> {
+        self.extensions().get()
+    }
+
+    fn content_length(&self) -> Option<u64> {
+        self.headers()
+            .get(header::CONTENT_LENGTH)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn bytes(self) -> impl Future<Output = Result<Bytes, DrupalkitError>> + Send {
+        self.into_body().bytes()
+    }
+
+    async fn text(self) -> Result<String, DrupalkitError> {
+        let bytes = self.into_body().bytes().await?;
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    async fn json<T>(self) -> Result<T, DrupalkitError>
+    where
+        T: DeserializeOwned,
+    {
+        DrupalkitError::decode(self.into_body().bytes().await?)
+    }
+
+    fn bytes_stream(self) -> ByteStream {
+        self.into_body().into_stream()
+    }
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use reqwest::Client;
+
+use crate::DrupalkitError;
+
+use super::{Body, Request, Response, Transport};
+
+/// Sends requests using a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Returns the underlying `reqwest::Client`.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, req: Request) -> Result<Response, DrupalkitError> {
+        let req = reqwest::Request::try_from(req.map(|body| match body.try_into_bytes() {
+            Ok(bytes) => reqwest::Body::from(bytes),
+            Err(body) => reqwest::Body::wrap(body),
+        }))?;
+        let response = self.client.execute(req).await?;
+
+        // Keep the final URL after following redirects.
+        let url = response.url().clone();
+        let mut response = http::Response::from(response).map(Body::wrap);
+        response.extensions_mut().insert(url);
+
+        Ok(response)
+    }
+}
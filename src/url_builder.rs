@@ -0,0 +1,174 @@
+use std::borrow::Cow;
+
+use url::Url;
+
+use crate::{BuildError, DrupalkitError};
+
+/// Parses and validates the base url of a `Drupalkit` instance.
+pub(crate) fn parse_base_url(base_url: &str) -> Result<Url, BuildError> {
+    let url = Url::parse(base_url).map_err(|err| {
+        BuildError::InvalidBaseUrl(format!("invalid base url {}: {}", base_url, err))
+    })?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(BuildError::InvalidBaseUrl(format!(
+            "base url must use the http or https scheme: {}",
+            base_url
+        )));
+    }
+
+    if url.host().is_none() {
+        return Err(BuildError::InvalidBaseUrl(format!(
+            "base url must have a host: {}",
+            base_url
+        )));
+    }
+
+    Ok(url)
+}
+
+/// Builds the full URL of a request.
+///
+/// The path of the base url is treated as a directory and the given path
+/// is resolved relative to it following RFC 3986, so Drupal installations
+/// in a subdirectory (e.g. `https://example.com/drupal`) work as expected.
+/// Absolute URLs are used as they are.
+///
+/// The query pairs are percent-encoded and appended to
+/// any query already present in the path.
+pub(crate) fn build_url(
+    base_url: &str,
+    path: &str,
+    query: &[(String, String)],
+) -> Result<Url, DrupalkitError> {
+    let mut base = Url::parse(base_url)?;
+
+    if !base.path().ends_with('/') {
+        let dir = format!("{}/", base.path());
+        base.set_path(&dir);
+    }
+
+    let mut url = base.join(path.trim_start_matches('/'))?;
+
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(query);
+    }
+
+    Ok(url)
+}
+
+/// Prefixes the path with the langcode, as done by Drupal's
+/// URL language negotiation, e.g. `/jsonapi` becomes `de/jsonapi`.
+///
+/// Absolute URLs and paths already starting with the
+/// langcode are returned as they are.
+pub(crate) fn prefix_langcode<'a>(path: &'a str, langcode: &str) -> Cow<'a, str> {
+    if langcode.is_empty() || Url::parse(path).is_ok() {
+        return Cow::Borrowed(path);
+    }
+
+    let relative = path.trim_start_matches('/');
+    let first_segment = relative.split(['/', '?', '#']).next().unwrap_or_default();
+
+    if first_segment == langcode {
+        return Cow::Borrowed(path);
+    }
+
+    Cow::Owned(format!("{}/{}", langcode, relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(base_url: &str, path: &str) -> String {
+        build_url(base_url, path, &[]).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(
+            build("https://example.com", "/jsonapi"),
+            "https://example.com/jsonapi"
+        );
+        assert_eq!(
+            build("https://example.com/", "/jsonapi"),
+            "https://example.com/jsonapi"
+        );
+        assert_eq!(
+            build("https://example.com/", "jsonapi"),
+            "https://example.com/jsonapi"
+        );
+        assert_eq!(build("https://example.com", ""), "https://example.com/");
+    }
+
+    #[test]
+    fn test_join_subdirectory() {
+        assert_eq!(
+            build("https://example.com/drupal", "/jsonapi/node/article"),
+            "https://example.com/drupal/jsonapi/node/article"
+        );
+        assert_eq!(
+            build("https://example.com/drupal/", "/jsonapi/node/article"),
+            "https://example.com/drupal/jsonapi/node/article"
+        );
+        assert_eq!(
+            build("https://example.com/drupal", "/jsonapi/../user/login"),
+            "https://example.com/drupal/user/login"
+        );
+    }
+
+    #[test]
+    fn test_absolute_url() {
+        assert_eq!(
+            build("https://example.com", "https://cdn.example.com/file.pdf"),
+            "https://cdn.example.com/file.pdf"
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        let query = vec![
+            ("filter[title]".to_owned(), "Hello & Goodbye".to_owned()),
+            ("page[limit]".to_owned(), "10".to_owned()),
+        ];
+
+        let url = build_url("https://example.com", "/jsonapi?include=uid", &query).unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://example.com/jsonapi?include=uid&filter%5Btitle%5D=Hello+%26+Goodbye&page%5Blimit%5D=10"
+        );
+    }
+
+    #[test]
+    fn test_parse_base_url() {
+        assert!(parse_base_url("https://example.com/drupal").is_ok());
+        assert!(parse_base_url("http://localhost:8080").is_ok());
+        assert!(parse_base_url("example.com").is_err());
+        assert!(parse_base_url("ftp://example.com").is_err());
+        assert!(parse_base_url("mailto:someone@example.com").is_err());
+    }
+
+    #[test]
+    fn test_prefix_langcode() {
+        assert_eq!(prefix_langcode("/jsonapi/node", "de"), "de/jsonapi/node");
+        assert_eq!(prefix_langcode("jsonapi?page=1", "de"), "de/jsonapi?page=1");
+        assert_eq!(prefix_langcode("", "de"), "de/");
+        assert_eq!(prefix_langcode("/de/jsonapi", "de"), "/de/jsonapi");
+        assert_eq!(prefix_langcode("/de?page=1", "de"), "/de?page=1");
+        assert_eq!(prefix_langcode("/dev/jsonapi", "de"), "de/dev/jsonapi");
+        assert_eq!(prefix_langcode("/jsonapi", ""), "/jsonapi");
+        assert_eq!(
+            prefix_langcode("https://cdn.example.com/file.pdf", "de"),
+            "https://cdn.example.com/file.pdf"
+        );
+        assert_eq!(
+            build(
+                "https://example.com/drupal",
+                &prefix_langcode("/jsonapi", "fr")
+            ),
+            "https://example.com/drupal/fr/jsonapi"
+        );
+    }
+}
@@ -1,5 +1,8 @@
-use drupal_kit::auth::BearerAuthStrategy;
+#![cfg(feature = "reqwest")]
+
+use drupal_kit::auth::{BearerAuthStrategy, SessionAuthStrategy};
 use drupal_kit::http_client::HttpClient;
+use drupal_kit::transport::ResponseExt;
 use drupal_kit::{auth::BasicAuthStrategy, Drupalkit};
 use http::Method;
 
@@ -79,3 +82,153 @@ async fn test_bearer_auth() {
     let text = res.text().await.expect("must get body");
     assert_eq!("world", text);
 }
+
+#[tokio::test]
+async fn test_session_auth() {
+    let mut server = mockito::Server::new_async().await;
+
+    let login_mock = server
+        .mock("POST", "/user/login")
+        .match_query(mockito::Matcher::UrlEncoded("_format".into(), "json".into()))
+        .match_body(mockito::Matcher::Json(
+            serde_json::json!({"name": "admin", "pass": "secret"}),
+        ))
+        .with_status(200)
+        .with_header("set-cookie", "SESSabc=123; Max-Age=2000000; path=/; HttpOnly")
+        .with_body(
+            r#"{"current_user": {"uid": "1", "name": "admin"}, "csrf_token": "csrf", "logout_token": "logout"}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .match_header("Cookie", "SESSabc=123")
+        .with_status(200)
+        .with_body("world")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let logout_mock = server
+        .mock("POST", "/user/logout")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("_format".into(), "json".into()),
+            mockito::Matcher::UrlEncoded("token".into(), "logout".into()),
+        ]))
+        .match_header("Cookie", "SESSabc=123")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let url = server.url();
+
+    #[cfg(not(feature = "consumer"))]
+    let mut client = Drupalkit::new(&url);
+
+    #[cfg(feature = "consumer")]
+    let mut client = Drupalkit::new(&url, None);
+
+    client.set_auth_strategy(SessionAuthStrategy::new("admin", "secret"));
+
+    for _ in 0..2 {
+        let res = client
+            .request(Method::GET, "/some-path", "", vec![])
+            .await
+            .expect("request must not fail");
+
+        assert!(res.status().is_success());
+    }
+
+    client.logout().await.expect("logout must not fail");
+
+    login_mock.assert_async().await;
+    mock.assert_async().await;
+    logout_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_session_auth_relogin() {
+    let mut server = mockito::Server::new_async().await;
+
+    let login_mock = server
+        .mock("POST", "/user/login")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_header("set-cookie", "SESSabc=123; path=/; HttpOnly")
+        .with_body(r#"{"csrf_token": "csrf", "logout_token": "logout"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let unauthorized_mock = server
+        .mock("GET", "/some-path")
+        .with_status(401)
+        .expect(1)
+        .create_async()
+        .await;
+
+    // The request is replayed after logging in again.
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let url = server.url();
+
+    #[cfg(not(feature = "consumer"))]
+    let mut client = Drupalkit::new(&url);
+
+    #[cfg(feature = "consumer")]
+    let mut client = Drupalkit::new(&url, None);
+
+    client.set_auth_strategy(SessionAuthStrategy::new("admin", "secret"));
+
+    let res = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert!(res.status().is_success());
+
+    login_mock.assert_async().await;
+    unauthorized_mock.assert_async().await;
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_session_auth_login_failed() {
+    let mut server = mockito::Server::new_async().await;
+
+    let login_mock = server
+        .mock("POST", "/user/login")
+        .match_query(mockito::Matcher::Any)
+        .with_status(400)
+        .with_body(r#"{"message": "Sorry, unrecognized username or password."}"#)
+        .create_async()
+        .await;
+
+    let url = server.url();
+
+    #[cfg(not(feature = "consumer"))]
+    let mut client = Drupalkit::new(&url);
+
+    #[cfg(feature = "consumer")]
+    let mut client = Drupalkit::new(&url, None);
+
+    client.set_auth_strategy(SessionAuthStrategy::new("admin", "wrong"));
+
+    let err = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect_err("request must fail");
+
+    login_mock.assert_async().await;
+
+    assert!(matches!(err, drupal_kit::DrupalkitError::Auth(_)));
+    assert!(err
+        .to_string()
+        .contains("unrecognized username or password"));
+}
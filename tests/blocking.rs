@@ -0,0 +1,106 @@
+#![cfg(all(feature = "reqwest", feature = "blocking"))]
+
+use drupal_kit::auth::BasicAuthStrategy;
+use drupal_kit::http_client::HttpRequestOption;
+use drupal_kit::{Builder, DrupalkitError};
+use http::Method;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    title: String,
+}
+
+#[test]
+fn test_blocking_request() {
+    let mut server = mockito::Server::new();
+
+    let mock = server
+        .mock("GET", "/node/1")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "_format".into(),
+            "json".into(),
+        ))
+        .match_header("Authorization", "Basic YWRtaW46c2VjcmV0")
+        .with_status(200)
+        .with_body(r#"{"title": "Some article"}"#)
+        .expect(2)
+        .create();
+
+    let mut client = Builder::new().set_base_url(&server.url()).build_blocking();
+    client.set_auth_strategy(BasicAuthStrategy::new("admin", Some("secret")));
+
+    let options = || {
+        vec![HttpRequestOption::Query(vec![(
+            "_format".to_owned(),
+            "json".to_owned(),
+        )])]
+    };
+
+    let res = client
+        .request(Method::GET, "/node/1", "", options())
+        .expect("request must not fail");
+
+    assert!(res.status().is_success());
+    assert_eq!(res.body().as_ref(), br#"{"title": "Some article"}"#);
+
+    // Clones share the runtime and the auth strategy.
+    let node: Node = std::thread::spawn(move || {
+        client
+            .clone()
+            .request_json(Method::GET, "/node/1", "", options())
+    })
+    .join()
+    .unwrap()
+    .expect("request must not fail");
+
+    mock.assert();
+
+    assert_eq!(node.title, "Some article");
+}
+
+#[test]
+fn test_blocking_request_failed() {
+    let mut server = mockito::Server::new();
+
+    let mock = server.mock("GET", "/node/2").with_status(404).create();
+
+    let client = Builder::new().set_base_url(&server.url()).build_blocking();
+
+    let err = client
+        .request_json::<Node>(Method::GET, "/node/2", "", vec![])
+        .expect_err("request must fail");
+
+    mock.assert();
+
+    assert!(matches!(err, DrupalkitError::Status(_)));
+}
+
+#[test]
+#[cfg(feature = "simple-oauth")]
+fn test_blocking_request_token() {
+    use drupal_kit::blocking::Drupalkit;
+    use drupal_kit::simple_oauth::SimpleOauthGrant;
+
+    let mut server = mockito::Server::new();
+
+    let mock = server.mock("POST", "/oauth/token")
+        .with_status(200)
+        .with_body(r#"{"token_type": "bearer", "expires_in": 3000, "access_token": "_access-token-value_"}"#)
+        .match_body(r#"client_id=_client_id_&client_secret=_client_secret_&grant_type=client_credentials"#)
+        .create();
+
+    let client = Drupalkit::new(&server.url(), Some("_client_id_"));
+
+    let res = client
+        .request_token(SimpleOauthGrant::ClientCredentials {
+            client_id: "_client_id_".to_owned(),
+            client_secret: "_client_secret_".into(),
+            scopes: vec![],
+        })
+        .expect("request token must not fail");
+
+    mock.assert();
+
+    assert_eq!(res.access_token.expose_secret(), "_access-token-value_");
+}
@@ -0,0 +1,286 @@
+#![cfg(feature = "reqwest")]
+
+use drupal_kit::auth::{AuthStrategy, AuthStrategyResult};
+use drupal_kit::cache::{CacheStatus, ClientCacheStatus, DrupalCacheStatus, MemoryCacheStore};
+use drupal_kit::http_client::{HttpClient, HttpRequestOption};
+use drupal_kit::transport::ResponseExt;
+use drupal_kit::{Builder, Drupalkit};
+use http::{header, HeaderValue, Method};
+
+fn client(server: &mockito::Server) -> Drupalkit {
+    Builder::new()
+        .set_base_url(&server.url())
+        .set_cache_store(MemoryCacheStore::new(100))
+        .build()
+}
+
+async fn get(client: &Drupalkit, options: Vec<HttpRequestOption>) -> (CacheStatus, String) {
+    let response = client
+        .request(Method::GET, "/jsonapi", "", options)
+        .await
+        .expect("request must not fail");
+
+    let status = *response
+        .extensions()
+        .get::<CacheStatus>()
+        .expect("cache status must be recorded");
+
+    (status, response.text().await.unwrap())
+}
+
+#[tokio::test]
+async fn test_fresh_response_is_served_from_cache() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi")
+        .with_status(200)
+        .with_header("cache-control", "public, max-age=300")
+        .with_header("x-drupal-cache", "MISS")
+        .with_header("x-drupal-dynamic-cache", "UNCACHEABLE (poor cacheability)")
+        .with_body("cached")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+
+    let (status, body) = get(&client, vec![]).await;
+    assert_eq!(status.client, ClientCacheStatus::Miss);
+    assert_eq!(status.page_cache, Some(DrupalCacheStatus::Miss));
+    assert_eq!(
+        status.dynamic_page_cache,
+        Some(DrupalCacheStatus::Uncacheable)
+    );
+    assert_eq!(body, "cached");
+
+    let (status, body) = get(&client.clone(), vec![]).await;
+    assert_eq!(status.client, ClientCacheStatus::Hit);
+    assert_eq!(body, "cached");
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_stale_response_is_revalidated() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi")
+        .match_header("if-none-match", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_header("cache-control", "no-cache")
+        .with_header("etag", "\"v1\"")
+        .with_body("original")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let not_modified_mock = server
+        .mock("GET", "/jsonapi")
+        .match_header("if-none-match", "\"v1\"")
+        .with_status(304)
+        .with_header("etag", "\"v1\"")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+
+    let (status, _) = get(&client, vec![]).await;
+    assert_eq!(status.client, ClientCacheStatus::Miss);
+
+    let (status, body) = get(&client, vec![]).await;
+    assert_eq!(status.client, ClientCacheStatus::Revalidated);
+    assert_eq!(body, "original");
+
+    mock.assert_async().await;
+    not_modified_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_authenticated_request_bypasses_cache() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi")
+        .with_status(200)
+        .with_header("cache-control", "public, max-age=300")
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+
+    let authorization = || {
+        vec![HttpRequestOption::Header(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer token"),
+        )]
+    };
+
+    let (status, _) = get(&client, authorization()).await;
+    assert_eq!(status.client, ClientCacheStatus::Bypass);
+
+    let (status, _) = get(&client, authorization()).await;
+    assert_eq!(status.client, ClientCacheStatus::Bypass);
+
+    // The authenticated responses were not stored for anonymous requests.
+    let (status, _) = get(&client, vec![]).await;
+    assert_eq!(status.client, ClientCacheStatus::Miss);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_auth_strategy_request_bypasses_cache() {
+    struct ApiKeyAuthStrategy;
+
+    #[async_trait::async_trait]
+    impl AuthStrategy for ApiKeyAuthStrategy {
+        async fn set_auth_info(
+            &mut self,
+            parts: &mut http::request::Parts,
+            _path: &str,
+            _options: Vec<HttpRequestOption>,
+            _drupalkit: &Drupalkit,
+        ) -> AuthStrategyResult {
+            parts
+                .headers
+                .insert("x-auth-token", HeaderValue::from_static("api-key"));
+
+            Ok(())
+        }
+    }
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi")
+        .match_header("x-auth-token", "api-key")
+        .with_status(200)
+        .with_header("cache-control", "public, max-age=300")
+        .with_body("authenticated")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let anonymous_mock = server
+        .mock("GET", "/jsonapi")
+        .match_header("x-auth-token", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_header("cache-control", "public, max-age=300")
+        .with_body("anonymous")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mut client = client(&server);
+    client.set_auth_strategy(ApiKeyAuthStrategy);
+
+    let (status, _) = get(&client, vec![]).await;
+    assert_eq!(status.client, ClientCacheStatus::Bypass);
+
+    let (status, _) = get(&client, vec![]).await;
+    assert_eq!(status.client, ClientCacheStatus::Bypass);
+
+    // Anonymous requests of the same client are cached.
+    let (status, body) = get(&client, vec![HttpRequestOption::Anonymous]).await;
+    assert_eq!(status.client, ClientCacheStatus::Miss);
+    assert_eq!(body, "anonymous");
+
+    let (status, body) = get(&client, vec![HttpRequestOption::Anonymous]).await;
+    assert_eq!(status.client, ClientCacheStatus::Hit);
+    assert_eq!(body, "anonymous");
+
+    mock.assert_async().await;
+    anonymous_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_uncacheable_response_is_not_stored() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi")
+        .with_status(200)
+        .with_header("cache-control", "no-store")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+
+    get(&client, vec![]).await;
+
+    let (status, _) = get(&client, vec![]).await;
+    assert_eq!(status.client, ClientCacheStatus::Miss);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_no_cache_option() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi")
+        .with_status(200)
+        .with_header("cache-control", "public, max-age=300")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+
+    get(&client, vec![]).await;
+
+    let (status, _) = get(&client, vec![HttpRequestOption::NoCache]).await;
+    assert_eq!(status.client, ClientCacheStatus::Bypass);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_invalidate_tags() {
+    let mut server = mockito::Server::new_async().await;
+
+    let node_mock = server
+        .mock("GET", "/jsonapi/node/article/12")
+        .with_status(200)
+        .with_header("cache-control", "public, max-age=300")
+        .with_header("x-drupal-cache-tags", "node:12 user:1 http_response")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let other_mock = server
+        .mock("GET", "/jsonapi/node/article/13")
+        .with_status(200)
+        .with_header("cache-control", "public, max-age=300")
+        .with_header("x-drupal-cache-tags", "node:13 user:1 http_response")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = client(&server);
+
+    for path in ["/jsonapi/node/article/12", "/jsonapi/node/article/13"] {
+        client
+            .request(Method::GET, path, "", vec![])
+            .await
+            .expect("request must not fail");
+    }
+
+    assert_eq!(client.invalidate_tags(&["node:12", "node_list"]).await, 1);
+
+    for path in ["/jsonapi/node/article/12", "/jsonapi/node/article/13"] {
+        client
+            .request(Method::GET, path, "", vec![])
+            .await
+            .expect("request must not fail");
+    }
+
+    node_mock.assert_async().await;
+    other_mock.assert_async().await;
+}
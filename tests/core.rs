@@ -1,4 +1,6 @@
-use drupal_kit::{http_client::HttpClient, Drupalkit};
+#![cfg(feature = "reqwest")]
+
+use drupal_kit::{http_client::HttpClient, transport::ResponseExt, Drupalkit};
 use http::Method;
 
 #[tokio::test]
@@ -30,3 +32,99 @@ async fn test_request() {
     let text = res.text().await.expect("must get body");
     assert_eq!("world", text);
 }
+
+#[tokio::test]
+async fn test_request_url() {
+    use drupal_kit::http_client::HttpRequestOption;
+    use drupal_kit::Builder;
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/drupal/jsonapi/node/article")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("include".into(), "uid".into()),
+            mockito::Matcher::UrlEncoded("filter[title]".into(), "Hello & Goodbye".into()),
+        ]))
+        .with_status(200)
+        .create_async()
+        .await;
+
+    // Base url with subdirectory and trailing slash.
+    let client = Builder::new()
+        .set_base_url(&format!("{}/drupal/", server.url()))
+        .build();
+
+    let res = client
+        .request(
+            Method::GET,
+            "/jsonapi/node/article?include=uid",
+            "",
+            vec![HttpRequestOption::Query(vec![(
+                "filter[title]".to_owned(),
+                "Hello & Goodbye".to_owned(),
+            )])],
+        )
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert!(res.status().is_success());
+}
+
+#[tokio::test]
+async fn test_builder_default_headers() {
+    use drupal_kit::auth::BearerAuthStrategy;
+    use drupal_kit::http_client::HttpRequestOption;
+    use drupal_kit::Builder;
+    use http::{HeaderMap, HeaderName, HeaderValue};
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .match_header("User-Agent", "my-app/1.0")
+        .match_header("X-Site", "docs")
+        .match_header("Accept", "application/vnd.api+json")
+        .match_header("Authorization", "Bearer abc123")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let mut default_headers = HeaderMap::new();
+    default_headers.insert("x-site", HeaderValue::from_static("main"));
+    default_headers.insert("accept", HeaderValue::from_static("application/json"));
+    default_headers.insert("user-agent", HeaderValue::from_static("overridden"));
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .with_default_headers(default_headers)
+        .with_user_agent("my-app/1.0")
+        .with_auth_strategy(BearerAuthStrategy::new("abc123"))
+        .try_build()
+        .expect("drupalkit must be valid");
+
+    let res = client
+        .request(
+            Method::GET,
+            "/some-path",
+            "",
+            vec![
+                HttpRequestOption::Header(
+                    HeaderName::from_static("x-site"),
+                    HeaderValue::from_static("docs"),
+                ),
+                HttpRequestOption::Header(
+                    HeaderName::from_static("accept"),
+                    HeaderValue::from_static("application/vnd.api+json"),
+                ),
+            ],
+        )
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert!(res.status().is_success());
+}
@@ -0,0 +1,235 @@
+#![cfg(feature = "reqwest")]
+
+use drupal_kit::auth::SessionAuthStrategy;
+use drupal_kit::http_client::{HttpClient, HttpRequestOption};
+use drupal_kit::transport::ResponseExt;
+use drupal_kit::{Builder, Drupalkit};
+use http::{header, HeaderValue, Method};
+use mockito::{Matcher, Mock, ServerGuard};
+
+async fn mock_login(server: &mut ServerGuard, expect: usize) -> Mock {
+    server
+        .mock("POST", "/user/login")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("set-cookie", "SESSabc=123; path=/; HttpOnly")
+        .with_body(r#"{"csrf_token": "csrf", "logout_token": "logout"}"#)
+        .expect(expect)
+        .create_async()
+        .await
+}
+
+fn session_client(server: &ServerGuard) -> Drupalkit {
+    let mut client = Builder::new().set_base_url(&server.url()).build();
+    client.set_auth_strategy(SessionAuthStrategy::new("admin", "secret"));
+
+    client
+}
+
+#[tokio::test]
+async fn test_csrf_token_unsafe_methods() {
+    let mut server = mockito::Server::new_async().await;
+
+    let login_mock = mock_login(&mut server, 1).await;
+
+    // The token returned by the login is used.
+    let token_mock = server
+        .mock("GET", "/session/token")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let get_mock = server
+        .mock("GET", "/node/1")
+        .match_header("X-CSRF-Token", Matcher::Missing)
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let post_mock = server
+        .mock("POST", "/node")
+        .match_header("Cookie", "SESSabc=123")
+        .match_header("X-CSRF-Token", "csrf")
+        .with_status(201)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = session_client(&server);
+
+    client
+        .request(Method::GET, "/node/1", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    // The token is shared by clones.
+    for client in [client.clone(), client] {
+        let res = client
+            .request(Method::POST, "/node", "{}", vec![])
+            .await
+            .expect("request must not fail");
+
+        assert_eq!(res.status(), 201);
+    }
+
+    login_mock.assert_async().await;
+    token_mock.assert_async().await;
+    get_mock.assert_async().await;
+    post_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_csrf_token_fetched_for_cookie() {
+    let mut server = mockito::Server::new_async().await;
+
+    let token_mock = server
+        .mock("GET", "/session/token")
+        .match_header("Cookie", "SESSabc=123")
+        .with_status(200)
+        .with_body("token-1")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let post_mock = server
+        .mock("POST", "/node")
+        .match_header("X-CSRF-Token", "token-1")
+        .with_status(201)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = Builder::new().set_base_url(&server.url()).build();
+
+    // The token of a session not started by the auth
+    // strategy is fetched lazily once.
+    for _ in 0..2 {
+        client
+            .request(
+                Method::POST,
+                "/node",
+                "{}",
+                vec![HttpRequestOption::Header(
+                    header::COOKIE,
+                    HeaderValue::from_static("SESSabc=123"),
+                )],
+            )
+            .await
+            .expect("request must not fail");
+    }
+
+    token_mock.assert_async().await;
+    post_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_csrf_token_refresh() {
+    let mut server = mockito::Server::new_async().await;
+
+    let _login_mock = mock_login(&mut server, 1).await;
+
+    let token_mock = server
+        .mock("GET", "/session/token")
+        .with_status(200)
+        .with_body("fresh")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let rejected_mock = server
+        .mock("PATCH", "/node/1")
+        .match_header("X-CSRF-Token", "csrf")
+        .with_status(403)
+        .with_body(r#"{"message":"X-CSRF-Token request header is invalid"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let mock = server
+        .mock("PATCH", "/node/1")
+        .match_header("X-CSRF-Token", "fresh")
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = session_client(&server);
+
+    let res = client
+        .request(Method::PATCH, "/node/1", "{}", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(res.status(), 200);
+
+    token_mock.assert_async().await;
+    rejected_mock.assert_async().await;
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_csrf_token_forbidden() {
+    let mut server = mockito::Server::new_async().await;
+
+    let _login_mock = mock_login(&mut server, 1).await;
+
+    // Other 403 responses are returned as is.
+    let mock = server
+        .mock("DELETE", "/node/1")
+        .with_status(403)
+        .with_body(r#"{"message":"The 'delete any article content' permission is required."}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = session_client(&server);
+
+    let res = client
+        .request(Method::DELETE, "/node/1", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(res.status(), 403);
+    assert!(res.text().await.unwrap().contains("permission is required"));
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_csrf_token_cleared_on_logout() {
+    let mut server = mockito::Server::new_async().await;
+
+    let login_mock = mock_login(&mut server, 2).await;
+
+    let logout_mock = server
+        .mock("POST", "/user/logout")
+        .match_query(Matcher::Any)
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let post_mock = server
+        .mock("POST", "/node")
+        .match_header("X-CSRF-Token", "csrf")
+        .with_status(201)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = session_client(&server);
+
+    client
+        .request(Method::POST, "/node", "{}", vec![])
+        .await
+        .expect("request must not fail");
+
+    client.logout().await.expect("logout must not fail");
+
+    client
+        .request(Method::POST, "/node", "{}", vec![])
+        .await
+        .expect("request must not fail");
+
+    login_mock.assert_async().await;
+    logout_mock.assert_async().await;
+    post_mock.assert_async().await;
+}
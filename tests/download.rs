@@ -0,0 +1,176 @@
+#![cfg(feature = "reqwest")]
+
+use std::sync::{Arc, Mutex};
+
+use drupal_kit::auth::BearerAuthStrategy;
+use drupal_kit::download::{DownloadOptions, DownloadProgress};
+use drupal_kit::Builder;
+
+#[tokio::test]
+async fn test_download_private_file() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/system/files/reports/report.txt")
+        .match_header("Authorization", "Bearer abc123")
+        .with_status(200)
+        .with_body("some report")
+        .create_async()
+        .await;
+
+    let mut client = Builder::new().set_base_url(&server.url()).build();
+    client.set_auth_strategy(BearerAuthStrategy::new("abc123"));
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let progress_reported = reported.clone();
+
+    let mut file = Vec::new();
+    let progress = client
+        .download_file(
+            "private://reports/report.txt",
+            &mut file,
+            DownloadOptions::new().set_on_progress(move |progress| {
+                progress_reported.lock().unwrap().push(progress);
+            }),
+        )
+        .await
+        .expect("download must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(file, b"some report");
+    assert_eq!(
+        progress,
+        DownloadProgress {
+            downloaded: 11,
+            total: Some(11),
+        }
+    );
+    assert_eq!(reported.lock().unwrap().last(), Some(&progress));
+}
+
+#[tokio::test]
+async fn test_download_public_file_resume() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/files/image.txt")
+        .match_header("Range", "bytes=5-")
+        .with_status(206)
+        .with_header("Content-Range", "bytes 5-10/11")
+        .with_body("report")
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_public_files_path("/files/")
+        .build();
+
+    let mut file = b"some ".to_vec();
+    let progress = client
+        .download_file(
+            "public://image.txt",
+            &mut file,
+            DownloadOptions::new().set_resume_from(5),
+        )
+        .await
+        .expect("download must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(file, b"some report");
+    assert_eq!(
+        progress,
+        DownloadProgress {
+            downloaded: 11,
+            total: Some(11),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_download_public_file_without_langcode() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/sites/default/files/image.txt")
+        .with_status(200)
+        .with_body("some image")
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_langcode("de")
+        .build();
+
+    let mut file = Vec::new();
+    client
+        .download_file("public://image.txt", &mut file, DownloadOptions::new())
+        .await
+        .expect("download must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(file, b"some image");
+}
+
+#[tokio::test]
+async fn test_download_resume_without_range_support() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/file.txt")
+        .with_status(200)
+        .with_body("some report")
+        .create_async()
+        .await;
+
+    let client = Builder::new().set_base_url(&server.url()).build();
+
+    let mut file = b"some ".to_vec();
+    client
+        .download_file(
+            "/file.txt",
+            &mut file,
+            DownloadOptions::new().set_resume_from(5),
+        )
+        .await
+        .expect("download must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(file, b"some report");
+}
+
+#[tokio::test]
+async fn test_download_other_host_is_anonymous() {
+    let server = mockito::Server::new_async().await;
+    let mut cdn = mockito::Server::new_async().await;
+
+    let mock = cdn
+        .mock("GET", "/file.txt")
+        .match_header("Authorization", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_body("from cdn")
+        .create_async()
+        .await;
+
+    let mut client = Builder::new().set_base_url(&server.url()).build();
+    client.set_auth_strategy(BearerAuthStrategy::new("abc123"));
+
+    let mut file = Vec::new();
+    client
+        .download_file(
+            &format!("{}/file.txt", cdn.url()),
+            &mut file,
+            DownloadOptions::new(),
+        )
+        .await
+        .expect("download must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(file, b"from cdn");
+}
@@ -1,26 +1,28 @@
+#![cfg(feature = "reqwest")]
+
 use drupal_kit::http::Method;
 use drupal_kit::http_client::{HttpClient, HttpRequestOption};
-use reqwest::Client;
-use http;
-use http::header;
+use drupal_kit::transport::{ReqwestTransport, Transport};
+use drupal_kit::DrupalkitError;
+use serde::Deserialize;
 
 struct TestHttpClient {
-    client: Client,
+    transport: ReqwestTransport,
     base_url: String,
 }
 
 impl TestHttpClient {
     fn new(base_url: String) -> Self {
         Self {
-            client: Client::new(),
+            transport: ReqwestTransport::default(),
             base_url,
         }
     }
 }
 
 impl HttpClient for TestHttpClient {
-    fn get_http_client(&self) -> &Client {
-        &self.client
+    fn get_transport(&self) -> &dyn Transport {
+        &self.transport
     }
 
     fn get_base_url(&self) -> &str {
@@ -84,3 +86,110 @@ async fn test_request_json_custom_content_type() {
     // The request should succeed because the mock expects the custom Content-Type header
     assert!(result.is_ok());
 }
+
+#[tokio::test]
+async fn test_request_json_status_error() {
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+
+    let _mock = server
+        .mock("GET", "/test")
+        .with_status(503)
+        .with_header("retry-after", "10")
+        .with_body("maintenance")
+        .create_async()
+        .await;
+
+    let client = TestHttpClient::new(base_url);
+
+    let result: Result<serde_json::Value, _> =
+        client.request_json(Method::GET, "/test", "", vec![]).await;
+
+    let err = result.expect_err("request must fail");
+
+    assert_eq!(err.status(), Some(http::StatusCode::SERVICE_UNAVAILABLE));
+    assert!(err.is_retryable());
+
+    match err {
+        DrupalkitError::Status(err) => {
+            assert_eq!(err.headers()["retry-after"], "10");
+            assert_eq!(err.body().as_ref(), b"maintenance");
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[tokio::test]
+async fn test_request_json_decode_error() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Payload {
+        data: Vec<Item>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Item {
+        id: String,
+    }
+
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+
+    let body = r#"{"data": [{"id": "a"}, {"id": 1}]}"#;
+
+    let _mock = server
+        .mock("GET", "/test")
+        .with_status(200)
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let client = TestHttpClient::new(base_url);
+
+    let result: Result<Payload, _> = client.request_json(Method::GET, "/test", "", vec![]).await;
+
+    let err = result.expect_err("request must fail");
+
+    assert_eq!(err.status(), None);
+    assert!(!err.is_retryable());
+
+    match err {
+        DrupalkitError::Decode {
+            body: raw, path, ..
+        } => {
+            assert_eq!(path, "data[1].id");
+            assert_eq!(raw.as_ref(), body.as_bytes());
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+}
+
+#[tokio::test]
+async fn test_request_stream() {
+    use futures_util::TryStreamExt;
+
+    let mut server = mockito::Server::new_async().await;
+    let base_url = server.url();
+
+    let _mock = server
+        .mock("GET", "/test")
+        .with_status(200)
+        .with_chunked_body(|w| {
+            w.write_all(b"hello ")?;
+            w.write_all(b"world")
+        })
+        .create_async()
+        .await;
+
+    let client = TestHttpClient::new(base_url);
+
+    let stream = client
+        .request_stream(Method::GET, "/test", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    let chunks: Vec<_> = stream.try_collect().await.expect("stream must not fail");
+
+    assert_eq!(chunks.concat(), b"hello world");
+}
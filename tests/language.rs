@@ -0,0 +1,142 @@
+#![cfg(feature = "reqwest")]
+
+use drupal_kit::http_client::{HttpClient, HttpRequestOption};
+use drupal_kit::language::{LangcodeMode, Language};
+use drupal_kit::Builder;
+use http::Method;
+use mockito::Matcher;
+
+#[tokio::test]
+async fn test_langcode_path_prefix() {
+    let mut server = mockito::Server::new_async().await;
+
+    let de_mock = server
+        .mock("GET", "/drupal/de/jsonapi/node/article")
+        .match_header("Accept-Language", Matcher::Missing)
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+    let fr_mock = server
+        .mock("GET", "/drupal/fr/jsonapi/node/article")
+        .with_status(200)
+        .create_async()
+        .await;
+    let default_mock = server
+        .mock("GET", "/drupal/jsonapi/node/article")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&format!("{}/drupal", server.url()))
+        .set_langcode("de")
+        .build();
+
+    let requests = [
+        ("/jsonapi/node/article", vec![]),
+        // Paths prefixed by the caller are not prefixed again.
+        ("/de/jsonapi/node/article", vec![]),
+        (
+            "/jsonapi/node/article",
+            vec![HttpRequestOption::Langcode("fr".to_owned())],
+        ),
+        // An empty langcode disables the default.
+        (
+            "/jsonapi/node/article",
+            vec![HttpRequestOption::Langcode(String::new())],
+        ),
+    ];
+
+    for (path, options) in requests {
+        let res = client
+            .request(Method::GET, path, "", options)
+            .await
+            .expect("request must not fail");
+
+        assert!(res.status().is_success());
+    }
+
+    de_mock.assert_async().await;
+    fr_mock.assert_async().await;
+    default_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_langcode_accept_language() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi/node/article")
+        .match_header("Accept-Language", "fr")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_langcode("de")
+        .set_langcode_mode(LangcodeMode::AcceptLanguage)
+        .build();
+
+    let res = client
+        .request(
+            Method::GET,
+            "/jsonapi/node/article",
+            "",
+            vec![HttpRequestOption::Langcode("fr".to_owned())],
+        )
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert!(res.status().is_success());
+}
+
+#[tokio::test]
+async fn test_languages() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi/configurable_language/configurable_language")
+        .match_query(Matcher::Missing)
+        .with_status(200)
+        .with_body(
+            r#"{"data": [
+                {"type": "configurable_language--configurable_language", "id": "b", "attributes": {"drupal_internal__id": "ar", "label": "Arabic", "direction": "rtl", "weight": 1, "locked": false}},
+                {"type": "configurable_language--configurable_language", "id": "a", "attributes": {"drupal_internal__id": "en", "label": "English", "direction": "ltr", "weight": 0, "locked": false}},
+                {"type": "configurable_language--configurable_language", "id": "c", "attributes": {"drupal_internal__id": "und", "label": "Not specified", "direction": "ltr", "weight": 2, "locked": true}}
+            ]}"#,
+        )
+        .create_async()
+        .await;
+
+    // The list is requested without language prefix.
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_langcode("de")
+        .build();
+
+    let languages = client.languages().await.expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(
+        languages,
+        vec![
+            Language {
+                langcode: "en".to_owned(),
+                label: "English".to_owned(),
+                direction: "ltr".to_owned(),
+                weight: 0,
+            },
+            Language {
+                langcode: "ar".to_owned(),
+                label: "Arabic".to_owned(),
+                direction: "rtl".to_owned(),
+                weight: 1,
+            },
+        ]
+    );
+}
@@ -0,0 +1,233 @@
+#![cfg(feature = "reqwest")]
+
+use std::time::Duration;
+
+use drupal_kit::http_client::HttpClient;
+use drupal_kit::{Builder, Drupalkit, DrupalkitError, MaintenancePolicy};
+use http::{Method, StatusCode};
+
+const MAINTENANCE_PAGE: &str = "<html><head><title>Site under maintenance | Drupal</title></head>\
+    <body>Drupal is currently under maintenance. We should be back shortly.</body></html>";
+
+fn client(server: &mockito::Server, max_wait: Duration) -> Drupalkit {
+    Builder::new()
+        .set_base_url(&server.url())
+        .set_maintenance_policy(
+            MaintenancePolicy::new()
+                .set_max_wait(max_wait)
+                .set_probe_path("/probe")
+                .set_probe_interval(Duration::from_millis(20)),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn test_maintenance_error() {
+    let mut server = mockito::Server::new_async().await;
+
+    server
+        .mock("GET", "/node/1")
+        .with_status(503)
+        .with_header("content-type", "text/html; charset=UTF-8")
+        .with_body(MAINTENANCE_PAGE)
+        .create_async()
+        .await;
+
+    server
+        .mock("GET", "/jsonapi/node/article")
+        .with_status(503)
+        .with_header("content-type", "application/vnd.api+json")
+        .with_body(r#"{"errors": [{"title": "Service Unavailable", "status": "503", "detail": "Drupal is currently under maintenance."}]}"#)
+        .create_async()
+        .await;
+
+    let drupalkit = Builder::new().set_base_url(&server.url()).build();
+
+    for path in ["/node/1", "/jsonapi/node/article"] {
+        let err = drupalkit
+            .request_json::<serde_json::Value>(Method::GET, path, "", vec![])
+            .await
+            .expect_err("request must fail");
+
+        assert!(err.is_retryable());
+        assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(matches!(err, DrupalkitError::Maintenance(_)), "{:?}", err);
+    }
+}
+
+#[tokio::test]
+async fn test_wait_until_online() {
+    let mut server = mockito::Server::new_async().await;
+
+    let maintenance_mock = server
+        .mock("GET", "/node/1")
+        .with_status(503)
+        .with_body(MAINTENANCE_PAGE)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mock = server
+        .mock("GET", "/node/1")
+        .with_status(200)
+        .with_body(r#"{"nid": 1}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let offline_probe_mock = server
+        .mock("GET", "/probe")
+        .with_status(503)
+        .with_body(MAINTENANCE_PAGE)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let online_probe_mock = server
+        .mock("GET", "/probe")
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let drupalkit = client(&server, Duration::from_secs(5));
+    let clone = drupalkit.clone();
+
+    let node: serde_json::Value = drupalkit
+        .request_json(Method::GET, "/node/1", "", vec![])
+        .await
+        .expect("request must be replayed once the site is online");
+
+    assert_eq!(node["nid"], 1);
+    assert!(!clone.get_maintenance_gate().unwrap().is_offline());
+
+    clone
+        .request(Method::GET, "/node/1", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    maintenance_mock.assert_async().await;
+    mock.assert_async().await;
+    offline_probe_mock.assert_async().await;
+    online_probe_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_max_wait() {
+    let mut server = mockito::Server::new_async().await;
+
+    server
+        .mock("GET", "/node/1")
+        .with_status(503)
+        .with_body(r#"{"message": "Drupal is currently under maintenance."}"#)
+        .create_async()
+        .await;
+
+    let probe_mock = server
+        .mock("GET", "/probe")
+        .with_status(503)
+        .with_body(MAINTENANCE_PAGE)
+        .expect_at_least(1)
+        .create_async()
+        .await;
+
+    let drupalkit = client(&server, Duration::from_millis(100));
+
+    let err = drupalkit
+        .request(Method::GET, "/node/1", "", vec![])
+        .await
+        .expect_err("request must fail once the maximum wait elapsed");
+
+    match err {
+        DrupalkitError::Maintenance(err) => {
+            assert!(err.is_maintenance());
+            assert_eq!(err.status(), StatusCode::SERVICE_UNAVAILABLE);
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+
+    // The pause ends, so the next request is sent again.
+    assert!(!drupalkit.get_maintenance_gate().unwrap().is_offline());
+
+    probe_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_other_unavailable_response_is_not_maintenance() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/node/1")
+        .with_status(503)
+        .with_body("Backend fetch failed")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let drupalkit = client(&server, Duration::from_secs(5));
+
+    let response = drupalkit
+        .request(Method::GET, "/node/1", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(!drupalkit.get_maintenance_gate().unwrap().is_offline());
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_other_jsonapi_error_is_not_maintenance() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi/node/article")
+        .with_status(503)
+        .with_header("content-type", "application/vnd.api+json")
+        .with_body(r#"{"errors": [{"title": "Service Unavailable", "status": "503", "detail": "The search backend is not available."}]}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let drupalkit = client(&server, Duration::from_secs(5));
+
+    let err = drupalkit
+        .request_json::<serde_json::Value>(Method::GET, "/jsonapi/node/article", "", vec![])
+        .await
+        .expect_err("request must fail");
+
+    assert!(matches!(err, DrupalkitError::Status(_)), "{:?}", err);
+    assert!(!drupalkit.get_maintenance_gate().unwrap().is_offline());
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_proxy_page_is_not_maintenance() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/node/1")
+        .with_status(503)
+        .with_header("content-type", "text/html")
+        .with_body(
+            "<html><head><title>503 Service Unavailable</title></head>\
+            <body>The origin is currently under maintenance.</body></html>",
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let drupalkit = client(&server, Duration::from_secs(5));
+
+    let err = drupalkit
+        .request_json::<serde_json::Value>(Method::GET, "/node/1", "", vec![])
+        .await
+        .expect_err("request must fail");
+
+    assert!(matches!(err, DrupalkitError::Status(_)), "{:?}", err);
+    assert!(!drupalkit.get_maintenance_gate().unwrap().is_offline());
+
+    mock.assert_async().await;
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "reqwest")]
+
+use async_trait::async_trait;
+use drupal_kit::http_client::HttpClient;
+use drupal_kit::middleware::{Middleware, Next, RequestContext};
+use drupal_kit::transport::{Request, Response, ResponseExt};
+use drupal_kit::{Builder, DrupalkitError};
+use http::{HeaderValue, Method};
+
+struct AppendHeaderMiddleware {
+    value: &'static str,
+}
+
+#[async_trait]
+impl Middleware for AppendHeaderMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        ctx: &RequestContext<'_>,
+        next: Next<'_>,
+    ) -> Result<Response, DrupalkitError> {
+        let value = match req.headers().get("x-chain") {
+            Some(current) => format!("{},{}", current.to_str().unwrap(), self.value),
+            None => self.value.to_owned(),
+        };
+
+        req.headers_mut()
+            .insert("x-chain", HeaderValue::from_str(&value).unwrap());
+
+        let mut response = next.run(req, ctx).await?;
+
+        response
+            .headers_mut()
+            .insert("x-path", HeaderValue::from_str(ctx.path).unwrap());
+
+        Ok(response)
+    }
+}
+
+struct ShortCircuitMiddleware;
+
+#[async_trait]
+impl Middleware for ShortCircuitMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        ctx: &RequestContext<'_>,
+        next: Next<'_>,
+    ) -> Result<Response, DrupalkitError> {
+        if ctx.path != "/short-circuit" {
+            return next.run(req, ctx).await;
+        }
+
+        let response = http::Response::builder()
+            .status(200)
+            .body("from middleware".into())
+            .unwrap();
+
+        Ok(response)
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_order() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(200)
+        .match_header("x-chain", "first,second")
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .with_middleware(AppendHeaderMiddleware { value: "first" })
+        .with_middleware(AppendHeaderMiddleware { value: "second" })
+        .build();
+
+    let res = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert!(res.status().is_success());
+    assert_eq!(res.headers()["x-path"], "/some-path");
+}
+
+#[tokio::test]
+async fn test_middleware_short_circuit() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/short-circuit")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .with_middleware(ShortCircuitMiddleware)
+        .build();
+
+    let res = client
+        .request(Method::GET, "/short-circuit", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert!(res.status().is_success());
+    assert_eq!(res.text().await.unwrap(), "from middleware");
+}
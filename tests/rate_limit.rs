@@ -0,0 +1,126 @@
+#![cfg(feature = "reqwest")]
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use drupal_kit::http_client::HttpClient;
+use drupal_kit::{Builder, RateLimit, RetryPolicy};
+use http::Method;
+
+#[tokio::test]
+async fn test_rate_limit_shared_across_clones() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(200)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_rate_limit(RateLimit::per_second(10).set_burst(1))
+        .build();
+
+    let other_client = client.clone();
+
+    let start = Instant::now();
+
+    let (first, second, third) = tokio::join!(
+        client.request(Method::GET, "/some-path", "", vec![]),
+        other_client.request(Method::GET, "/some-path", "", vec![]),
+        client.request(Method::GET, "/some-path", "", vec![]),
+    );
+
+    first.expect("request must not fail");
+    second.expect("request must not fail");
+    third.expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert!(start.elapsed() >= Duration::from_millis(190));
+}
+
+#[tokio::test]
+async fn test_path_rate_limit() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/jsonapi")
+        .with_status(200)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_path_rate_limit("/oauth/token", RateLimit::per_minute(1))
+        .build();
+
+    let start = Instant::now();
+
+    for _ in 0..3 {
+        client
+            .request(Method::GET, "/jsonapi", "", vec![])
+            .await
+            .expect("request must not fail");
+    }
+
+    mock.assert_async().await;
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_rate_limit_applies_to_retries() {
+    let mut server = mockito::Server::new_async().await;
+
+    let attempts = Arc::new(Mutex::new(Vec::new()));
+    let recorded = attempts.clone();
+
+    let failing_mock = server
+        .mock("GET", "/some-path")
+        .with_status(429)
+        .with_body_from_request(move |_| {
+            recorded.lock().unwrap().push(Instant::now());
+
+            Vec::new()
+        })
+        .expect(2)
+        .create_async()
+        .await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_rate_limit(RateLimit::per_second(10).set_burst(1))
+        .set_retry_policy(
+            RetryPolicy::new()
+                .set_max_attempts(3)
+                .set_backoff(Duration::from_millis(1), Duration::from_millis(1))
+                .set_jitter(false),
+        )
+        .build();
+
+    let start = Instant::now();
+
+    client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    failing_mock.assert_async().await;
+    mock.assert_async().await;
+
+    let attempts = attempts.lock().unwrap();
+
+    assert!(attempts[1].duration_since(attempts[0]) >= Duration::from_millis(90));
+    assert!(start.elapsed() >= Duration::from_millis(190));
+}
@@ -0,0 +1,169 @@
+#![cfg(feature = "reqwest")]
+
+use drupal_kit::http_client::HttpRequestOption;
+use drupal_kit::transport::ResponseExt;
+use drupal_kit::{Builder, DrupalkitError};
+use http::{HeaderName, HeaderValue};
+use mockito::Matcher;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+struct NewArticle<'a> {
+    title: &'a str,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Article {
+    nid: u32,
+    title: String,
+}
+
+#[tokio::test]
+async fn test_get() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/node/1")
+        .match_query(Matcher::UrlEncoded("_format".into(), "json".into()))
+        .match_header("Content-Type", Matcher::Missing)
+        .match_body("")
+        .with_status(200)
+        .with_body(r#"{"nid": 1, "title": "Hello"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = Builder::new().set_base_url(&server.url()).build();
+    let format = || HttpRequestOption::Query(vec![("_format".to_owned(), "json".to_owned())]);
+
+    let article: Article = client
+        .get("/node/1")
+        .option(format())
+        .send_json()
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(
+        article,
+        Article {
+            nid: 1,
+            title: "Hello".to_owned()
+        }
+    );
+
+    let res = client
+        .get("/node/1")
+        .option(format())
+        .send()
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(res.text().await.unwrap(), r#"{"nid": 1, "title": "Hello"}"#);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_post_json() {
+    let mut server = mockito::Server::new_async().await;
+
+    let json_mock = server
+        .mock("POST", "/node")
+        .match_header("Content-Type", "application/json")
+        .match_body(Matcher::Json(serde_json::json!({"title": "Hello"})))
+        .with_status(201)
+        .with_body(r#"{"nid": 2, "title": "Hello"}"#)
+        .create_async()
+        .await;
+
+    let jsonapi_mock = server
+        .mock("PATCH", "/jsonapi/node/article/2")
+        .match_header("Content-Type", "application/vnd.api+json")
+        .match_body(Matcher::Json(serde_json::json!({"title": "Updated"})))
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let client = Builder::new().set_base_url(&server.url()).build();
+
+    let article: Article = client
+        .post("/node")
+        .json(&NewArticle { title: "Hello" })
+        .send_json()
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(article.nid, 2);
+
+    // An explicit Content-Type takes precedence.
+    client
+        .patch("/jsonapi/node/article/2")
+        .option(HttpRequestOption::Header(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/vnd.api+json"),
+        ))
+        .json(&NewArticle { title: "Updated" })
+        .send_empty()
+        .await
+        .expect("request must not fail");
+
+    json_mock.assert_async().await;
+    jsonapi_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_delete() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("DELETE", "/node/2")
+        .match_header("Content-Type", Matcher::Missing)
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let forbidden_mock = server
+        .mock("DELETE", "/node/3")
+        .with_status(403)
+        .with_body(r#"{"message": "Access denied"}"#)
+        .create_async()
+        .await;
+
+    let client = Builder::new().set_base_url(&server.url()).build();
+
+    client
+        .delete("/node/2")
+        .send_empty()
+        .await
+        .expect("request must not fail");
+
+    let err = client
+        .delete("/node/3")
+        .send_empty()
+        .await
+        .expect_err("request must fail");
+
+    mock.assert_async().await;
+    forbidden_mock.assert_async().await;
+
+    assert!(matches!(err, DrupalkitError::Status(_)));
+    assert_eq!(err.status(), Some(http::StatusCode::FORBIDDEN));
+}
+
+#[tokio::test]
+async fn test_json_encode_error() {
+    let client = Builder::new().set_base_url("http://localhost").build();
+
+    // JSON object keys must be strings.
+    let body = std::collections::BTreeMap::from([((1, 2), "value")]);
+
+    let err = client
+        .post("/node")
+        .json(&body)
+        .send_empty()
+        .await
+        .expect_err("request must fail");
+
+    assert!(matches!(err, DrupalkitError::Encode(_)), "{:?}", err);
+    assert!(std::error::Error::source(&err).is_some());
+}
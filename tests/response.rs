@@ -0,0 +1,119 @@
+#![cfg(feature = "reqwest")]
+
+use drupal_kit::cache::{ClientCacheStatus, DrupalCacheStatus, MemoryCacheStore};
+use drupal_kit::http_client::{CacheMaxAge, DrupalResponse, HttpClient};
+use drupal_kit::{Builder, DrupalkitError};
+use http::{Method, StatusCode};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Node {
+    nid: u32,
+}
+
+#[tokio::test]
+async fn test_request_json_with_meta() {
+    let mut server = mockito::Server::new_async().await;
+
+    server
+        .mock("GET", "/node/1")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header(
+            "x-drupal-cache-tags",
+            "node:1 config:system.site http_response",
+        )
+        .with_header(
+            "x-drupal-cache-contexts",
+            "url.query_args:_format user.permissions",
+        )
+        .with_header("x-drupal-cache-max-age", "-1 (Permanent)")
+        .with_header("x-drupal-cache", "MISS")
+        .with_header("x-drupal-dynamic-cache", "HIT")
+        .with_header("x-custom", "value")
+        .with_body(r#"{"nid": 1}"#)
+        .create_async()
+        .await;
+
+    let drupalkit = Builder::new().set_base_url(&server.url()).build();
+
+    let response: DrupalResponse<Node> = drupalkit
+        .request_json_with_meta(Method::GET, "/node/1", "", vec![])
+        .await
+        .unwrap();
+
+    assert_eq!(response.data, Node { nid: 1 });
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers["x-custom"], "value");
+    assert_eq!(response.url.as_ref().map(|url| url.path()), Some("/node/1"));
+    assert_eq!(
+        response.cache_tags,
+        vec!["node:1", "config:system.site", "http_response"]
+    );
+    assert_eq!(
+        response.cache_contexts,
+        vec!["url.query_args:_format", "user.permissions"]
+    );
+    assert_eq!(response.max_age, Some(CacheMaxAge::Permanent));
+    assert_eq!(response.page_cache, Some(DrupalCacheStatus::Miss));
+    assert_eq!(response.dynamic_page_cache, Some(DrupalCacheStatus::Hit));
+    assert_eq!(response.client_cache, None);
+}
+
+#[tokio::test]
+async fn test_max_age_falls_back_to_cache_control() {
+    let mut server = mockito::Server::new_async().await;
+
+    server
+        .mock("GET", "/node/1")
+        .with_status(200)
+        .with_header("cache-control", "max-age=300, public")
+        .with_body(r#"{"nid": 1}"#)
+        .create_async()
+        .await;
+
+    let drupalkit = Builder::new()
+        .set_base_url(&server.url())
+        .set_cache_store(MemoryCacheStore::new(10))
+        .build();
+
+    let response = drupalkit
+        .get("/node/1")
+        .send_json_with_meta::<Node>()
+        .await
+        .unwrap();
+
+    assert_eq!(response.max_age, Some(CacheMaxAge::Seconds(300)));
+    assert_eq!(response.client_cache, Some(ClientCacheStatus::Miss));
+    assert!(response.cache_tags.is_empty());
+    assert_eq!(response.page_cache, None);
+
+    let response = drupalkit
+        .get("/node/1")
+        .send_json_with_meta::<Node>()
+        .await
+        .unwrap();
+
+    assert_eq!(response.client_cache, Some(ClientCacheStatus::Hit));
+    assert_eq!(response.into_data(), Node { nid: 1 });
+}
+
+#[tokio::test]
+async fn test_request_json_with_meta_fails_on_error_status() {
+    let mut server = mockito::Server::new_async().await;
+
+    server
+        .mock("GET", "/node/1")
+        .with_status(404)
+        .with_body(r#"{"message": "Not found"}"#)
+        .create_async()
+        .await;
+
+    let drupalkit = Builder::new().set_base_url(&server.url()).build();
+
+    let result = drupalkit
+        .request_json_with_meta::<Node>(Method::GET, "/node/1", "", vec![])
+        .await;
+
+    assert!(matches!(result, Err(DrupalkitError::Status(_))));
+}
@@ -0,0 +1,206 @@
+#![cfg(feature = "reqwest")]
+
+use std::time::Duration;
+
+use drupal_kit::http_client::{HttpClient, HttpRequestOption};
+use drupal_kit::{Builder, RetryPolicy};
+use http::Method;
+
+fn retry_policy() -> RetryPolicy {
+    RetryPolicy::new()
+        .set_max_attempts(3)
+        .set_backoff(Duration::from_millis(1), Duration::from_millis(10))
+}
+
+#[tokio::test]
+async fn test_retry_until_success() {
+    let mut server = mockito::Server::new_async().await;
+
+    let failing_mock = server
+        .mock("GET", "/some-path")
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(200)
+        .with_body("world")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_retry_policy(retry_policy())
+        .build();
+
+    let res = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    failing_mock.assert_async().await;
+    mock.assert_async().await;
+
+    assert!(res.status().is_success());
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_after_max_attempts() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(502)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_retry_policy(retry_policy())
+        .build();
+
+    let res = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(res.status(), 502);
+}
+
+#[tokio::test]
+async fn test_retry_after_exceeding_max_backoff() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(429)
+        .with_header("retry-after", "3600")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_retry_policy(retry_policy())
+        .build();
+
+    let res = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(res.status(), 429);
+}
+
+#[tokio::test]
+async fn test_no_retry_for_non_idempotent_method() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/some-path")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_retry_policy(retry_policy())
+        .build();
+
+    let res = client
+        .request(Method::POST, "/some-path", "payload", vec![])
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    assert_eq!(res.status(), 503);
+}
+
+#[tokio::test]
+async fn test_no_retry_for_internal_server_error() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(500)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_retry_policy(retry_policy())
+        .build();
+
+    let err = client
+        .request_json::<serde_json::Value>(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect_err("request must fail");
+
+    mock.assert_async().await;
+
+    // The policy and the error agree on what is retryable.
+    assert_eq!(err.status(), Some(http::StatusCode::INTERNAL_SERVER_ERROR));
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_retry_request_options() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/some-path")
+        .with_status(503)
+        .match_body("payload")
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .set_retry_policy(retry_policy())
+        .build();
+
+    // Retry non-idempotent request with a request specific policy.
+    let policy = retry_policy().set_retry_non_idempotent(true);
+    client
+        .request(
+            Method::POST,
+            "/some-path",
+            "payload",
+            vec![HttpRequestOption::Retry(policy)],
+        )
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    let mock = server
+        .mock("GET", "/no-retry")
+        .with_status(503)
+        .expect(1)
+        .create_async()
+        .await;
+
+    client
+        .request(
+            Method::GET,
+            "/no-retry",
+            "",
+            vec![HttpRequestOption::NoRetry],
+        )
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+}
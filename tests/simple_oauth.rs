@@ -1,3 +1,5 @@
+#![cfg(feature = "reqwest")]
+
 #[tokio::test]
 #[cfg(feature = "simple-oauth")]
 async fn test_client_credentials_grant() {
@@ -18,12 +20,12 @@ async fn test_client_credentials_grant() {
 
     let url = server.url();
 
-    let client = Drupalkit::new(&url, Some(&client_id));
+    let client = Drupalkit::new(&url, Some(client_id));
 
     let res = client
         .request_token(SimpleOauthGrant::ClientCredentials {
             client_id: client_id.to_owned(),
-            client_secret: client_secret.to_owned(),
+            client_secret: client_secret.into(),
             scopes,
         })
         .await;
@@ -32,10 +34,10 @@ async fn test_client_credentials_grant() {
 
     let res = res.expect("request token must not fail");
 
-    assert_eq!(res.access_token, "_access-token-value_");
+    assert_eq!(res.access_token.expose_secret(), "_access-token-value_");
     assert_eq!(res.expires_in, 3000);
     assert_eq!(res.token_type, "bearer");
-    assert_eq!(res.refresh_token, None);
+    assert!(res.refresh_token.is_none());
 }
 
 #[tokio::test]
@@ -58,12 +60,12 @@ async fn test_client_credentials_grant_no_scopes() {
 
     let url = server.url();
 
-    let client = Drupalkit::new(&url, Some(&client_id));
+    let client = Drupalkit::new(&url, Some(client_id));
 
     let res = client
         .request_token(SimpleOauthGrant::ClientCredentials {
             client_id: client_id.to_owned(),
-            client_secret: client_secret.to_owned(),
+            client_secret: client_secret.into(),
             scopes,
         })
         .await;
@@ -72,10 +74,10 @@ async fn test_client_credentials_grant_no_scopes() {
 
     let res = res.expect("request token must not fail");
 
-    assert_eq!(res.access_token, "_access-token-value_");
+    assert_eq!(res.access_token.expose_secret(), "_access-token-value_");
     assert_eq!(res.expires_in, 3000);
     assert_eq!(res.token_type, "bearer");
-    assert_eq!(res.refresh_token, None);
+    assert!(res.refresh_token.is_none());
 }
 
 #[tokio::test]
@@ -99,13 +101,13 @@ async fn test_refresh_token_grant() {
 
     let url = server.url();
 
-    let client = Drupalkit::new(&url, Some(&client_id));
+    let client = Drupalkit::new(&url, Some(client_id));
 
     let res = client
         .request_token(SimpleOauthGrant::RefreshToken {
             client_id: client_id.to_owned(),
-            client_secret: client_secret.to_owned(),
-            refresh_token: refresh_token.to_owned(),
+            client_secret: client_secret.into(),
+            refresh_token: refresh_token.into(),
             scopes,
         })
         .await;
@@ -114,17 +116,15 @@ async fn test_refresh_token_grant() {
 
     let res = res.expect("request token must not fail");
 
-    assert_eq!(res.access_token, "_access-token-value_");
+    assert_eq!(res.access_token.expose_secret(), "_access-token-value_");
     assert_eq!(res.expires_in, 3000);
     assert_eq!(res.token_type, "bearer");
-    assert_eq!(res.refresh_token, None);
+    assert!(res.refresh_token.is_none());
 }
 
 #[tokio::test]
 #[cfg(feature = "simple-oauth")]
 async fn test_client_credentials_auth_strategy() {
-    use std::sync::Arc;
-
     use drupal_kit::http_client::HttpClient;
     use drupal_kit::simple_oauth::{ClientCredentialsAuthStrategy, SimpleOauthGrant};
     use drupal_kit::Drupalkit;
@@ -154,7 +154,7 @@ async fn test_client_credentials_auth_strategy() {
 
     let url = server.url();
 
-    let mut client = Drupalkit::new(&url, Some(&client_id));
+    let mut client = Drupalkit::new(&url, Some(client_id));
 
     let auth_strategy =
         ClientCredentialsAuthStrategy::new(client_id, client_secret, scopes.clone());
@@ -164,8 +164,8 @@ async fn test_client_credentials_auth_strategy() {
     _ = client
         .request_token(SimpleOauthGrant::RefreshToken {
             client_id: client_id.to_owned(),
-            client_secret: client_secret.to_owned(),
-            refresh_token: refresh_token.to_owned(),
+            client_secret: client_secret.into(),
+            refresh_token: refresh_token.into(),
             scopes,
         })
         .await;
@@ -182,3 +182,125 @@ async fn test_client_credentials_auth_strategy() {
     token_mock.assert_async().await;
     mock.assert_async().await;
 }
+
+#[tokio::test]
+#[cfg(feature = "simple-oauth")]
+async fn test_client_credentials_auth_strategy_revoked_token() {
+    use drupal_kit::http_client::HttpClient;
+    use drupal_kit::simple_oauth::ClientCredentialsAuthStrategy;
+    use drupal_kit::Drupalkit;
+    use http::Method;
+
+    let mut server = mockito::Server::new_async().await;
+    let client_id = "_client_id_";
+    let client_secret = "_client_secret_";
+
+    let revoked_token_mock = server
+        .mock("POST", "/oauth/token")
+        .with_status(200)
+        .with_body(r#"{"token_type": "bearer", "expires_in": 3000, "access_token": "_revoked_"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let token_mock = server
+        .mock("POST", "/oauth/token")
+        .with_status(200)
+        .with_body(r#"{"token_type": "bearer", "expires_in": 3000, "access_token": "_valid_"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let unauthorized_mock = server
+        .mock("GET", "/authenticated")
+        .with_status(401)
+        .match_header("Authorization", "Bearer _revoked_")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mock = server
+        .mock("GET", "/authenticated")
+        .with_status(200)
+        .match_header("Authorization", "Bearer _valid_")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let url = server.url();
+
+    let mut client = Drupalkit::new(&url, Some(client_id));
+    client.set_auth_strategy(ClientCredentialsAuthStrategy::new(
+        client_id,
+        client_secret,
+        vec![],
+    ));
+
+    // The revoked token is dropped and the request replayed with a new token.
+    let res = client
+        .request(Method::GET, "/authenticated", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(res.status(), 200);
+
+    // The new token is cached.
+    let res = client
+        .request(Method::GET, "/authenticated", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(res.status(), 200);
+
+    revoked_token_mock.assert_async().await;
+    token_mock.assert_async().await;
+    unauthorized_mock.assert_async().await;
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+#[cfg(feature = "simple-oauth")]
+async fn test_client_credentials_auth_strategy_replays_once() {
+    use drupal_kit::http_client::HttpClient;
+    use drupal_kit::simple_oauth::ClientCredentialsAuthStrategy;
+    use drupal_kit::Drupalkit;
+    use http::Method;
+
+    let mut server = mockito::Server::new_async().await;
+    let client_id = "_client_id_";
+    let client_secret = "_client_secret_";
+
+    let token_mock = server
+        .mock("POST", "/oauth/token")
+        .with_status(200)
+        .with_body(r#"{"token_type": "bearer", "expires_in": 3000, "access_token": "_token_"}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let mock = server
+        .mock("GET", "/forbidden")
+        .with_status(401)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let url = server.url();
+
+    let mut client = Drupalkit::new(&url, Some(client_id));
+    client.set_auth_strategy(ClientCredentialsAuthStrategy::new(
+        client_id,
+        client_secret,
+        vec![],
+    ));
+
+    let res = client
+        .request(Method::GET, "/forbidden", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(res.status(), 401);
+
+    token_mock.assert_async().await;
+    mock.assert_async().await;
+}
@@ -0,0 +1,270 @@
+#![cfg(all(feature = "reqwest", feature = "testing"))]
+
+use std::path::PathBuf;
+
+use drupal_kit::auth::{BasicAuthStrategy, SessionAuthStrategy};
+use drupal_kit::http_client::{HttpClient, HttpRequestOption};
+use drupal_kit::testing::{RecordingTransport, ReplayTransport};
+use drupal_kit::transport::{ReqwestTransport, ResponseExt};
+use drupal_kit::{Builder, DrupalkitError};
+use http::Method;
+use serde_json::{json, Value};
+
+fn fixture_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("drupal_kit_{}_{}.json", name, std::process::id()))
+}
+
+#[tokio::test]
+async fn test_record_and_replay() {
+    let mut server = mockito::Server::new_async().await;
+    let fixture = fixture_path("record_and_replay");
+
+    let login_mock = server
+        .mock("POST", "/user/login")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "_format".into(),
+            "json".into(),
+        ))
+        .with_status(200)
+        .with_header("set-cookie", "SESS123=abc; path=/; HttpOnly")
+        .with_body(r#"{"current_user":{"uid":"1"},"csrf_token":"csrf-secret"}"#)
+        .create_async()
+        .await;
+
+    let article_mock = server
+        .mock("GET", "/jsonapi/node/article")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"data":[]}"#)
+        .create_async()
+        .await;
+
+    let login_body = json!({"name": "admin", "pass": "hunter2"}).to_string();
+    let login_options = || {
+        vec![HttpRequestOption::Query(vec![(
+            "_format".to_owned(),
+            "json".to_owned(),
+        )])]
+    };
+    let article_options = || {
+        vec![HttpRequestOption::Query(vec![
+            ("page[limit]".to_owned(), "10".to_owned()),
+            ("sort".to_owned(), "-created".to_owned()),
+        ])]
+    };
+
+    let mut client = Builder::new()
+        .set_base_url(&server.url())
+        .set_transport(RecordingTransport::new(
+            ReqwestTransport::default(),
+            &fixture,
+        ))
+        .build();
+    client.set_auth_strategy(BasicAuthStrategy::new("admin", Some("hunter2")));
+
+    let login: Value = client
+        .request_json(
+            Method::POST,
+            "/user/login",
+            login_body.clone(),
+            login_options(),
+        )
+        .await
+        .expect("request must not fail");
+    client
+        .request(Method::GET, "/jsonapi/node/article", "", article_options())
+        .await
+        .expect("request must not fail");
+
+    login_mock.assert_async().await;
+    article_mock.assert_async().await;
+
+    assert_eq!(login["csrf_token"], "csrf-secret");
+
+    let recorded = std::fs::read_to_string(&fixture).unwrap();
+
+    assert!(!recorded.contains("hunter2"));
+    assert!(!recorded.contains("csrf-secret"));
+    assert!(!recorded.contains("SESS123=abc"));
+    assert!(recorded.contains("SESS123=[REDACTED]; path=/; HttpOnly"));
+    assert!(!recorded.contains("Basic "));
+
+    // Replay against another host, without the mock server.
+    let transport = ReplayTransport::from_file(&fixture).expect("fixture must be valid");
+    std::fs::remove_file(&fixture).unwrap();
+
+    let client = Builder::new()
+        .set_base_url("https://drupal.example.com")
+        .set_transport(transport)
+        .build();
+
+    let login: Value = client
+        .request_json(Method::POST, "/user/login", login_body, login_options())
+        .await
+        .expect("request must match the recording");
+
+    assert_eq!(login["current_user"]["uid"], "1");
+    assert_eq!(login["csrf_token"], "[REDACTED]");
+
+    let response = client
+        .request(Method::GET, "/jsonapi/node/article", "", article_options())
+        .await
+        .expect("request must match the recording");
+
+    assert_eq!(
+        response.url().unwrap().as_str(),
+        "https://drupal.example.com/jsonapi/node/article?page%5Blimit%5D=10&sort=-created"
+    );
+    assert_eq!(response.text().await.unwrap(), r#"{"data":[]}"#);
+}
+
+#[tokio::test]
+async fn test_record_and_replay_session_login() {
+    let mut server = mockito::Server::new_async().await;
+    let fixture = fixture_path("record_and_replay_session_login");
+
+    let login_mock = server
+        .mock("POST", "/user/login")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "_format".into(),
+            "json".into(),
+        ))
+        .with_status(200)
+        .with_header("set-cookie", "SESS123=abc; path=/; HttpOnly")
+        .with_body(r#"{"current_user":{"uid":"1"},"csrf_token":"csrf-secret","logout_token":"logout-secret"}"#)
+        .create_async()
+        .await;
+
+    let article_mock = server
+        .mock("GET", "/jsonapi/node/article")
+        .match_header("cookie", "SESS123=abc")
+        .with_status(200)
+        .with_body(r#"{"data":[]}"#)
+        .create_async()
+        .await;
+
+    let mut client = Builder::new()
+        .set_base_url(&server.url())
+        .set_transport(RecordingTransport::new(
+            ReqwestTransport::default(),
+            &fixture,
+        ))
+        .build();
+    client.set_auth_strategy(SessionAuthStrategy::new("admin", "hunter2"));
+
+    client
+        .request(Method::GET, "/jsonapi/node/article", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    login_mock.assert_async().await;
+    article_mock.assert_async().await;
+
+    let recorded = std::fs::read_to_string(&fixture).unwrap();
+
+    assert!(!recorded.contains("hunter2"));
+    assert!(!recorded.contains("SESS123=abc"));
+
+    let transport = ReplayTransport::from_file(&fixture).expect("fixture must be valid");
+    std::fs::remove_file(&fixture).unwrap();
+
+    let mut client = Builder::new()
+        .set_base_url("https://drupal.example.com")
+        .set_transport(transport)
+        .build();
+    client.set_auth_strategy(SessionAuthStrategy::new("admin", "hunter2"));
+
+    let response = client
+        .request(Method::GET, "/jsonapi/node/article", "", vec![])
+        .await
+        .expect("replayed login must set a session cookie");
+
+    assert_eq!(response.text().await.unwrap(), r#"{"data":[]}"#);
+}
+
+#[tokio::test]
+async fn test_replay_unmatched_request() {
+    let fixture = r#"{
+        "interactions": [
+            {
+                "request": {
+                    "method": "POST",
+                    "path": "/node",
+                    "query": [["_format", "json"]],
+                    "body": "{\"title\":\"First\",\"type\":\"article\"}"
+                },
+                "response": {"status": 201, "body": "created"}
+            }
+        ]
+    }"#;
+
+    let transport = std::sync::Arc::new(ReplayTransport::from_json(fixture).unwrap());
+
+    let client = Builder::new()
+        .set_base_url("https://example.com")
+        .set_transport(SharedReplay(transport.clone()))
+        .build();
+
+    let options = || {
+        vec![HttpRequestOption::Query(vec![(
+            "_format".to_owned(),
+            "json".to_owned(),
+        )])]
+    };
+
+    let err = client
+        .request(
+            Method::POST,
+            "/node",
+            r#"{"title":"Second","type":"article"}"#,
+            options(),
+        )
+        .await
+        .expect_err("request must not match");
+
+    assert!(matches!(err, DrupalkitError::Transport(_)));
+    assert!(err
+        .to_string()
+        .contains(r#"POST /node?_format=json with body {"title":"Second","type":"article"}"#));
+
+    let result = std::panic::catch_unwind(|| transport.assert_all_replayed());
+    assert!(result.is_err());
+
+    // JSON bodies match regardless of the order of their keys.
+    let response = client
+        .request(
+            Method::POST,
+            "/node",
+            r#"{"type":"article","title":"First"}"#,
+            options(),
+        )
+        .await
+        .expect("request must match the recording");
+
+    assert_eq!(response.status(), 201);
+    transport.assert_all_replayed();
+
+    // Every interaction is replayed once.
+    client
+        .request(
+            Method::POST,
+            "/node",
+            r#"{"type":"article","title":"First"}"#,
+            options(),
+        )
+        .await
+        .expect_err("interaction must be replayed once");
+}
+
+/// Shares the transport with the test to assert all interactions were replayed.
+struct SharedReplay(std::sync::Arc<ReplayTransport>);
+
+#[async_trait::async_trait]
+impl drupal_kit::transport::Transport for SharedReplay {
+    async fn send(
+        &self,
+        req: drupal_kit::transport::Request,
+    ) -> Result<drupal_kit::transport::Response, DrupalkitError> {
+        self.0.send(req).await
+    }
+}
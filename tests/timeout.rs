@@ -0,0 +1,84 @@
+#![cfg(feature = "reqwest")]
+
+use std::net::TcpListener;
+use std::time::Duration;
+
+use drupal_kit::http_client::{HttpClient, HttpRequestOption};
+use drupal_kit::{Builder, DrupalkitError};
+use http::Method;
+use tokio_util::sync::CancellationToken;
+
+/// Returns the URL of a server accepting connections without ever responding.
+fn unresponsive_server() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("must bind listener");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    (listener, url)
+}
+
+#[tokio::test]
+async fn test_default_timeout() {
+    let (_listener, url) = unresponsive_server();
+
+    let client = Builder::new()
+        .set_base_url(&url)
+        .set_timeout(Duration::from_millis(50))
+        .build();
+
+    let err = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect_err("request must time out");
+
+    assert!(matches!(err, DrupalkitError::Timeout));
+    assert!(err.is_timeout());
+}
+
+#[tokio::test]
+async fn test_request_timeout() {
+    let (_listener, url) = unresponsive_server();
+
+    let client = Builder::new()
+        .set_base_url(&url)
+        .set_timeout(Duration::from_secs(60))
+        .build();
+
+    let err = client
+        .request_json::<serde_json::Value>(
+            Method::GET,
+            "/some-path",
+            "",
+            vec![HttpRequestOption::Timeout(Duration::from_millis(50))],
+        )
+        .await
+        .expect_err("request must time out");
+
+    assert!(err.is_timeout());
+}
+
+#[tokio::test]
+async fn test_request_cancel() {
+    let (_listener, url) = unresponsive_server();
+
+    let client = Builder::new().set_base_url(&url).build();
+
+    let token = CancellationToken::new();
+
+    let cancel_token = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        cancel_token.cancel();
+    });
+
+    let err = client
+        .request(
+            Method::GET,
+            "/some-path",
+            "",
+            vec![HttpRequestOption::Cancel(token)],
+        )
+        .await
+        .expect_err("request must be cancelled");
+
+    assert!(matches!(err, DrupalkitError::Cancelled));
+}
@@ -0,0 +1,90 @@
+#![cfg(all(feature = "reqwest", feature = "tower"))]
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use drupal_kit::http_client::HttpClient;
+use drupal_kit::service::{DrupalRequest, DrupalService};
+use drupal_kit::transport::{Response, ResponseExt};
+use drupal_kit::{Builder, DrupalkitError};
+use http::Method;
+use tower::timeout::TimeoutLayer;
+use tower::{layer::layer_fn, service_fn, Service, ServiceExt};
+
+#[tokio::test]
+async fn test_layers_wrap_request() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/some-path")
+        .with_status(200)
+        .with_body("world")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let layer_counter = counter.clone();
+
+    let client = Builder::new()
+        .set_base_url(&server.url())
+        .with_layer(layer_fn(move |inner: DrupalService| {
+            let counter = layer_counter.clone();
+
+            service_fn(move |req: DrupalRequest| {
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                inner.clone().oneshot(req)
+            })
+        }))
+        .build();
+
+    // Requests made with `HttpClient::request` are passed through the layers.
+    let res = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert!(res.status().is_success());
+
+    // As well as requests made with `Drupalkit` as a service.
+    let mut service = client.clone();
+    let res = service
+        .ready()
+        .await
+        .expect("service must be ready")
+        .call(DrupalRequest::new(Method::GET, "/some-path", "", vec![]))
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(res.text().await.unwrap(), "world");
+
+    mock.assert_async().await;
+
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_layer_error() {
+    let client = Builder::new()
+        .set_base_url("http://127.0.0.1:1")
+        .with_layer(TimeoutLayer::new(Duration::from_millis(10)))
+        .with_layer(layer_fn(|_inner: DrupalService| {
+            service_fn(|_req: DrupalRequest| async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+
+                Ok::<Response, DrupalkitError>(Response::new("".into()))
+            })
+        }))
+        .build();
+
+    let err = client
+        .request(Method::GET, "/some-path", "", vec![])
+        .await
+        .expect_err("request must time out");
+
+    assert!(matches!(err, DrupalkitError::Middleware(_)));
+}
@@ -0,0 +1,177 @@
+#![cfg(all(feature = "reqwest", feature = "tracing"))]
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use drupal_kit::http_client::HttpClient;
+use drupal_kit::Builder;
+use http::Method;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+/// Recorded fields of all spans, by span name.
+#[derive(Clone, Default)]
+struct Spans(Arc<Mutex<HashMap<String, HashMap<String, String>>>>);
+
+impl Spans {
+    fn field(&self, span: &str, field: &str) -> Option<String> {
+        self.0.lock().unwrap().get(span)?.get(field).cloned()
+    }
+
+    fn contains(&self, needle: &str) -> bool {
+        self.0
+            .lock()
+            .unwrap()
+            .values()
+            .flat_map(HashMap::values)
+            .any(|value| value.contains(needle))
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_owned(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.to_owned());
+    }
+}
+
+impl<S> Layer<S> for Spans
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let name = ctx.span(id).unwrap().name().to_owned();
+        let mut spans = self.0.lock().unwrap();
+
+        attrs.record(&mut FieldVisitor(spans.entry(name).or_default()));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let name = ctx.span(id).unwrap().name().to_owned();
+        let mut spans = self.0.lock().unwrap();
+
+        values.record(&mut FieldVisitor(spans.entry(name).or_default()));
+    }
+}
+
+#[tokio::test]
+async fn test_request_span() {
+    let spans = Spans::default();
+    let _guard = tracing_subscriber::registry()
+        .with(spans.clone())
+        .set_default();
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/node/12")
+        .match_query(mockito::Matcher::Any)
+        .match_header(
+            "traceparent",
+            mockito::Matcher::Regex("^00-[0-9a-f]{32}-[0-9a-f]{16}-01$".to_owned()),
+        )
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let client = Builder::new().set_base_url(&server.url()).build();
+
+    client
+        .request(Method::GET, "/node/12?token=_secret_", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    mock.assert_async().await;
+
+    let field = |name| spans.field("drupalkit.request", name);
+
+    assert_eq!(field("method").as_deref(), Some("GET"));
+    assert_eq!(field("route").as_deref(), Some("/node/{id}"));
+    assert_eq!(field("status").as_deref(), Some("404"));
+    assert_eq!(field("retries").as_deref(), Some("0"));
+    assert!(field("duration_ms").is_some());
+    assert_eq!(field("trace_id").map(|id| id.len()), Some(32));
+    assert!(!spans.contains("_secret_"));
+}
+
+#[tokio::test]
+#[cfg(feature = "simple-oauth")]
+async fn test_auth_source() {
+    use drupal_kit::simple_oauth::ClientCredentialsAuthStrategy;
+
+    let spans = Spans::default();
+    let _guard = tracing_subscriber::registry()
+        .with(spans.clone())
+        .set_default();
+
+    let mut server = mockito::Server::new_async().await;
+
+    let token_mock = server
+        .mock("POST", "/oauth/token")
+        .with_status(200)
+        .with_body(
+            r#"{"token_type": "bearer", "expires_in": 3000, "access_token": "_access-token_"}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mock = server
+        .mock("GET", "/jsonapi")
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let mut client = Builder::new().set_base_url(&server.url()).build();
+    client.set_auth_strategy(ClientCredentialsAuthStrategy::new(
+        "_client-id_",
+        "_client-secret_",
+        vec![],
+    ));
+
+    client
+        .request(Method::GET, "/jsonapi", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(
+        spans.field("drupalkit.request", "auth").as_deref(),
+        Some("fresh_grant")
+    );
+    assert_eq!(
+        spans
+            .field("drupalkit.request_token", "grant_type")
+            .as_deref(),
+        Some("client_credentials")
+    );
+
+    client
+        .request(Method::GET, "/jsonapi", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(
+        spans.field("drupalkit.request", "auth").as_deref(),
+        Some("cached_token")
+    );
+
+    token_mock.assert_async().await;
+    mock.assert_async().await;
+
+    assert!(!spans.contains("_client-secret_"));
+    assert!(!spans.contains("_access-token_"));
+}
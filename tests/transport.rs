@@ -0,0 +1,110 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use drupal_kit::auth::BasicAuthStrategy;
+use drupal_kit::http_client::HttpClient;
+use drupal_kit::transport::{Request, Response, ResponseExt, Transport};
+use drupal_kit::{Builder, DrupalkitError, RetryPolicy};
+use http::{header, Method, StatusCode};
+
+/// Method, URI, `Authorization` header and body of a request.
+type RecordedRequest = (Method, String, Option<String>, String);
+
+/// Records the requests and answers with the given statuses in order.
+#[derive(Clone, Default)]
+struct MockTransport {
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    statuses: Arc<Mutex<Vec<StatusCode>>>,
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn send(&self, req: Request) -> Result<Response, DrupalkitError> {
+        let (parts, body) = req.into_parts();
+        let authorization = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .map(|value| value.to_str().unwrap().to_owned());
+        let body = String::from_utf8(body.bytes().await?.to_vec()).unwrap();
+
+        self.requests.lock().unwrap().push((
+            parts.method,
+            parts.uri.to_string(),
+            authorization,
+            body,
+        ));
+
+        let status = self
+            .statuses
+            .lock()
+            .unwrap()
+            .pop()
+            .unwrap_or(StatusCode::OK);
+
+        Ok(http::Response::builder()
+            .status(status)
+            .body("mocked".into())?)
+    }
+}
+
+#[tokio::test]
+async fn test_custom_transport() {
+    let transport = MockTransport::default();
+
+    let mut client = Builder::new()
+        .set_base_url("https://example.com/drupal")
+        .set_transport(transport.clone())
+        .build();
+
+    client.set_auth_strategy(BasicAuthStrategy::new("admin", Some("secret")));
+
+    let response = client
+        .request(Method::POST, "/node", "payload", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(
+        response.url().map(|url| url.as_str()),
+        Some("https://example.com/drupal/node")
+    );
+    assert_eq!(response.text().await.unwrap(), "mocked");
+
+    let requests = transport.requests.lock().unwrap();
+
+    assert_eq!(
+        *requests,
+        vec![(
+            Method::POST,
+            "https://example.com/drupal/node".to_owned(),
+            Some("Basic YWRtaW46c2VjcmV0".to_owned()),
+            "payload".to_owned(),
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_custom_transport_retry() {
+    let transport = MockTransport::default();
+    transport
+        .statuses
+        .lock()
+        .unwrap()
+        .push(StatusCode::SERVICE_UNAVAILABLE);
+
+    let client = Builder::new()
+        .set_base_url("https://example.com")
+        .set_transport(transport.clone())
+        .set_retry_policy(
+            RetryPolicy::new().set_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+        )
+        .build();
+
+    let response = client
+        .request(Method::GET, "/jsonapi", "", vec![])
+        .await
+        .expect("request must not fail");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(transport.requests.lock().unwrap().len(), 2);
+}